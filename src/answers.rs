@@ -0,0 +1,239 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    input,
+    runner::{Outcome, RunResult},
+    solution::Answer,
+};
+
+pub fn answers_path() -> PathBuf {
+    input::input_dir().join("answers.toml")
+}
+
+/**
+ * Expected answers keyed by day and part. Stored on disk as a small subset of
+ * TOML, one table per day:
+ *
+ *  [day9]
+ *  part1 = 2005352194
+ *  part2 = "some text answer"
+ */
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+    // the part has no solver yet, so there's nothing to check or record
+    Unsolved,
+    Error(String),
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Answers, String> {
+        let mut result = Answers::default();
+        let mut day: Option<u8> = None;
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {:?}", i + 1, msg, line);
+            if let Some(header) = line.strip_prefix('[') {
+                let n = header
+                    .strip_suffix(']')
+                    .and_then(|h| h.trim().strip_prefix("day"))
+                    .and_then(|d| d.parse::<u8>().ok())
+                    .ok_or_else(|| err("expected a [dayN] header"))?;
+                day = Some(n);
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| err("expected part = answer"))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse::<u8>().ok())
+                .ok_or_else(|| err("expected a partN key"))?;
+            let day = day.ok_or_else(|| err("answer outside of a [dayN] table"))?;
+            let value = parse_value(value.trim()).ok_or_else(|| err("invalid answer value"))?;
+            result.entries.insert((day, part), value);
+        }
+        Ok(result)
+    }
+
+    /**
+     * Loads answers from disk. A missing file is treated as having no answers yet.
+     */
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers, String> {
+        match fs::read_to_string(&path) {
+            Ok(s) => Answers::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.as_ref().display(), e)),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries.get(&(day, part)).map(|s| s.as_str())
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: &Answer) {
+        self.entries.insert((day, part), answer.to_string());
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        let mut current_day = None;
+        for ((day, part), value) in &self.entries {
+            if current_day != Some(*day) {
+                if current_day.is_some() {
+                    out.push('\n');
+                }
+                out += &format!("[day{}]\n", day);
+                current_day = Some(*day);
+            }
+            out += &format!("part{} = {}\n", part, format_value(value));
+        }
+        out
+    }
+
+    pub fn check(&self, result: &RunResult) -> Verdict {
+        Verdict::of(result, self.get(result.task.day, result.task.part))
+    }
+}
+
+impl Verdict {
+    /**
+     * Compares a result with the expected answer, if there is one
+     */
+    pub fn of(result: &RunResult, expected: Option<&str>) -> Verdict {
+        let answer = match &result.outcome {
+            Outcome::Solved(a) => a.to_string(),
+            Outcome::Unsolved => return Verdict::Unsolved,
+            outcome => return Verdict::Error(outcome.error().unwrap_or_default()),
+        };
+        match expected {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+// numbers are written bare, anything else as a quoted string
+fn format_value(value: &str) -> String {
+    if value.parse::<i64>().is_ok() || value.parse::<u64>().is_ok() {
+        return value.to_string();
+    }
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    match value.strip_prefix('"') {
+        Some(quoted) => {
+            let mut result = String::new();
+            let mut chars = quoted.chars();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => result.push(chars.next()?),
+                    c => result.push(c),
+                }
+            }
+            if chars.as_str().trim().is_empty() {
+                Some(result)
+            } else {
+                None
+            }
+        }
+        None if value.parse::<i64>().is_ok() || value.parse::<u64>().is_ok() => {
+            Some(value.to_string())
+        }
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::Task;
+
+    const SAMPLE: &str = "# expected answers
+[day1]
+part1 = 54630
+part2 = 54770
+
+[day9]
+part2 = -1077
+part1 = \"with \\\"quotes\\\"\"
+";
+
+    fn solved(day: u8, part: u8, answer: Answer) -> RunResult {
+        RunResult {
+            task: Task { day, part },
+            outcome: Outcome::Solved(answer),
+            elapsed: Duration::ZERO,
+            memory: None,
+        }
+    }
+
+    #[test]
+    pub fn test_parse() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+        assert_eq!(answers.get(1, 1), Some("54630"));
+        assert_eq!(answers.get(9, 2), Some("-1077"));
+        assert_eq!(answers.get(9, 1), Some("with \"quotes\""));
+        assert_eq!(answers.get(2, 1), None);
+    }
+
+    #[test]
+    pub fn test_round_trip() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    pub fn test_parse_errors() {
+        assert!(Answers::parse("part1 = 5").is_err());
+        assert!(Answers::parse("[day1]\npart1 = five").is_err());
+        assert!(Answers::parse("[dayx]").is_err());
+    }
+
+    #[test]
+    pub fn test_check() {
+        let mut answers = Answers::parse(SAMPLE).unwrap();
+        assert_eq!(
+            answers.check(&solved(1, 1, 54630_u64.into())),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&solved(1, 2, 1_u64.into())),
+            Verdict::Fail {
+                expected: String::from("54770")
+            }
+        );
+        assert_eq!(answers.check(&solved(3, 1, 1_u64.into())), Verdict::Unknown);
+        answers.set(3, 1, &Answer::from(1_u64));
+        assert_eq!(answers.check(&solved(3, 1, 1_u64.into())), Verdict::Pass);
+        let unsolved = RunResult {
+            outcome: Outcome::Unsolved,
+            ..solved(1, 1, Answer::Unsolved)
+        };
+        assert_eq!(answers.check(&unsolved), Verdict::Unsolved);
+    }
+}
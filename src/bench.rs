@@ -0,0 +1,248 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    input::{self, InputSource},
+    memstats::MemStats,
+    runner::{self, Outcome, Task},
+};
+
+pub fn baseline_path() -> PathBuf {
+    input::input_dir().join("bench_baseline.txt")
+}
+
+/**
+ * Summary statistics over the timings of repeated runs
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        // nearest-rank percentile
+        let p95_rank = (n * 95).div_ceil(100);
+        Some(Stats {
+            runs: n,
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            p95: sorted[p95_rank.max(1) - 1],
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BenchResult {
+    pub task: Task,
+    // why there are no timings, if a run didn't produce an answer
+    pub stats: Result<Stats, Outcome>,
+    // the memory use of the last timed run, if allocations are being counted
+    pub memory: Option<MemStats>,
+}
+
+/**
+ * Runs a solver `warmup` times without recording, then `runs` times recording
+ * the timing of each. The input is read once up front so only the solver is
+ * measured. An unsolved part stops at its first run, so it's never timed.
+ */
+pub fn bench(task: Task, source: &InputSource, warmup: usize, runs: usize) -> BenchResult {
+    let mut memory = None;
+    let loaded = runner::load(task, source).map_err(Outcome::Failed);
+    let stats = loaded.and_then(|(solution, input)| {
+        let mut samples = Vec::with_capacity(runs);
+        for i in 0..warmup + runs {
+            let result = runner::solve(task, solution, &input);
+            if result.outcome.error().is_some() {
+                return Err(result.outcome);
+            }
+            if i >= warmup {
+                samples.push(result.elapsed);
+                memory = result.memory;
+            }
+        }
+        Stats::from_samples(&samples)
+            .ok_or_else(|| Outcome::Failed(String::from("no runs recorded")))
+    });
+    BenchResult {
+        task,
+        stats,
+        memory,
+    }
+}
+
+/**
+ * Median timings from a previous benchmark, keyed by day and part
+ */
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: BTreeMap<Task, Duration>,
+}
+
+impl Baseline {
+    pub fn parse(s: &str) -> Result<Baseline, String> {
+        let mut result = Baseline::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let values: Vec<u64> = line
+                .split_ascii_whitespace()
+                .map(|v| v.parse::<u64>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("line {}: expected numbers: {:?}", i + 1, line))?;
+            match values[..] {
+                [day, part, median_ns] if day <= 25 && part <= 2 => {
+                    let task = Task {
+                        day: day as u8,
+                        part: part as u8,
+                    };
+                    result.medians.insert(task, Duration::from_nanos(median_ns));
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected day part median_ns: {:?}",
+                        i + 1,
+                        line
+                    ))
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /**
+     * Loads a baseline from disk. A missing file is treated as an empty baseline.
+     */
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Baseline, String> {
+        match fs::read_to_string(&path) {
+            Ok(s) => Baseline::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.as_ref().display(), e)),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = String::from("# day part median_ns\n");
+        for (task, median) in &self.medians {
+            out += &format!("{} {} {}\n", task.day, task.part, median.as_nanos());
+        }
+        fs::write(path, out)
+    }
+
+    pub fn get(&self, task: Task) -> Option<Duration> {
+        self.medians.get(&task).copied()
+    }
+
+    /**
+     * Records the median of every successful result
+     */
+    pub fn update(&mut self, results: &[BenchResult]) {
+        for result in results {
+            if let Ok(stats) = &result.stats {
+                self.medians.insert(result.task, stats.median);
+            }
+        }
+    }
+}
+
+/**
+ * Percentage change of the median from the baseline, positive being slower
+ */
+pub fn change_percent(baseline: Duration, median: Duration) -> f64 {
+    let base = baseline.as_nanos().max(1) as f64;
+    (median.as_nanos() as f64 - base) / base * 100.0
+}
+
+/**
+ * A result regressed if its median is slower than the baseline by more than
+ * `threshold` percent
+ */
+pub fn is_regression(result: &BenchResult, baseline: &Baseline, threshold: f64) -> bool {
+    match (&result.stats, baseline.get(result.task)) {
+        (Ok(stats), Some(base)) => change_percent(base, stats.median) > threshold,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(v: &[u64]) -> Vec<Duration> {
+        v.iter().map(|m| Duration::from_micros(*m)).collect()
+    }
+
+    #[test]
+    pub fn test_stats() {
+        let stats = Stats::from_samples(&micros(&[
+            5, 1, 3, 2, 4,
+        ]))
+        .unwrap();
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+
+        let stats = Stats::from_samples(&micros(&[4, 1, 2, 3])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&micros(&samples)).unwrap();
+        assert_eq!(stats.p95, Duration::from_micros(95));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    pub fn test_baseline_parse() {
+        let baseline = Baseline::parse("# day part median_ns\n5 1 1200\n5 2 3400\n").unwrap();
+        assert_eq!(
+            baseline.get(Task { day: 5, part: 2 }),
+            Some(Duration::from_nanos(3400))
+        );
+        assert_eq!(baseline.get(Task { day: 6, part: 1 }), None);
+        assert!(Baseline::parse("5 1").is_err());
+        assert!(Baseline::parse("5 1 abc").is_err());
+    }
+
+    #[test]
+    pub fn test_change_percent() {
+        let base = Duration::from_micros(100);
+        assert_eq!(change_percent(base, Duration::from_micros(120)), 20.0);
+        assert_eq!(change_percent(base, Duration::from_micros(90)), -10.0);
+    }
+
+    #[test]
+    pub fn test_is_regression() {
+        let baseline = Baseline::parse("1 1 100000").unwrap();
+        let result = |median_us: u64| BenchResult {
+            task: Task { day: 1, part: 1 },
+            stats: Ok(Stats::from_samples(&micros(&[median_us])).unwrap()),
+            memory: None,
+        };
+        assert!(is_regression(&result(111), &baseline, 10.0));
+        assert!(!is_regression(&result(109), &baseline, 10.0));
+        assert!(!is_regression(&result(50), &baseline, 10.0));
+    }
+}
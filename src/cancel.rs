@@ -0,0 +1,103 @@
+use std::{
+    cell::RefCell,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/**
+ * Tells a running solver to stop. The runner hands one to each solver it runs
+ * under a timeout and cancels it once the deadline passes; long loops notice by
+ * calling `checkpoint`.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/**
+ * The panic payload `checkpoint` unwinds with, so the runner can tell a
+ * cancelled solver from one that panicked
+ */
+#[derive(Debug)]
+pub struct Cancelled;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+// puts the previous token back when dropped, even if `f` unwinds
+struct Restore(Option<CancelToken>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|c| *c.borrow_mut() = previous);
+    }
+}
+
+/**
+ * Runs `f` with `token` as the current thread's cancellation token
+ */
+pub fn with_token<T>(token: CancelToken, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|c| c.borrow_mut().replace(token));
+    let _restore = Restore(previous);
+    f()
+}
+
+/**
+ * Whether the current thread's token, if it has one, has been cancelled
+ */
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|c| c.borrow().as_ref().is_some_and(|t| t.is_cancelled()))
+}
+
+/**
+ * Stops the current solver if it has been cancelled, by unwinding with
+ * `Cancelled`. Cheap enough to call on every iteration of a long loop. Solvers
+ * don't need to handle it: the runner catches it and reports a timeout.
+ */
+pub fn checkpoint() {
+    if is_cancelled() {
+        // resume_unwind skips the panic hook, so nothing is printed
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_checkpoint() {
+        // without a token there's nothing to cancel
+        checkpoint();
+
+        let token = CancelToken::new();
+        let result = panic::catch_unwind(|| {
+            with_token(token.clone(), || {
+                checkpoint();
+                token.cancel();
+                checkpoint();
+                unreachable!("checkpoint should have unwound");
+            })
+        });
+        assert!(result.unwrap_err().downcast_ref::<Cancelled>().is_some());
+        // the token is gone once with_token is left, even by unwinding
+        assert!(!is_cancelled());
+    }
+}
@@ -0,0 +1,787 @@
+use std::time::Duration;
+
+use adventofcode2023::input::InputSource;
+use adventofcode2023::log::Level;
+use adventofcode2023::report::Format;
+
+pub const USAGE: &str = "Usage:
+    cargo run <day> <part>            run a single part
+    cargo run <day>                   run both parts of a day
+    cargo run <from>-<to> [part]      run a range of days
+    cargo run all [part]              run every registered day
+    cargo run verify [days] [part]    check answers against input/answers.toml
+    cargo run bench [days] [part]     time repeated runs of each solver
+    cargo run new <day>               generate src/day<day>.rs and register it
+    cargo run watch <day> <part>      re-run a part whenever its input or source changes
+    cargo run fetch <days>            download puzzle inputs that aren't saved yet
+    cargo run submit <day> <part>     solve a part and submit the answer
+    cargo run repl <day>              load a day's input and explore it interactively
+
+Options:
+    --input <file>   read the input from a file instead of input/<day>.txt, or
+                     from stdin if the file is -. Only valid for a single day.
+                     The input directory can be changed with AOC_INPUT_DIR.
+    --example[=<n>]  run against the day's nth built-in example instead of the
+                     input, the first by default. verify checks every example.
+    --jobs <n>       run up to n solvers at once (run-all and verify), default 1
+    --format <f>     print results as text (the default), json or csv records
+    --mem            count each solver's allocations and peak memory use, also
+                     per run when benchmarking
+    --timeout <s>    give up on a solver after s seconds and report it as timed out
+    -v, -vv, -vvv    log solver progress to stderr, at info, debug or trace level
+    --record         (verify) store newly computed answers as the expected ones
+    --runs <n>       (bench) number of timed runs, default 20
+    --warmup <n>     (bench) number of untimed runs first, default 3
+    --save           (bench) save the medians as the new baseline
+    --threshold <p>  (bench) percent slowdown flagged as a regression, default 10";
+
+#[derive(Debug, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    pub input: InputSource,
+    pub log_level: Level,
+    pub format: Format,
+    pub jobs: usize,
+    pub mem: bool,
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Solve {
+        day: u8,
+        part: u8,
+    },
+    RunAll {
+        days: Vec<u8>,
+        part: Option<u8>,
+    },
+    Verify {
+        days: Vec<u8>,
+        part: Option<u8>,
+        record: bool,
+    },
+    Bench {
+        days: Vec<u8>,
+        part: Option<u8>,
+        options: BenchOptions,
+    },
+    New {
+        day: u8,
+    },
+    Watch {
+        day: u8,
+        part: u8,
+    },
+    Fetch {
+        days: Vec<u8>,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+    },
+    Repl {
+        day: u8,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub warmup: usize,
+    pub save: bool,
+    pub threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            runs: 20,
+            warmup: 3,
+            save: false,
+            threshold: 10.0,
+        }
+    }
+}
+
+impl Command {
+    /**
+     * The days selected by the command
+     */
+    pub fn days(&self) -> &[u8] {
+        match self {
+            Command::Solve { day, .. }
+            | Command::New { day }
+            | Command::Watch { day, .. }
+            | Command::Submit { day, .. }
+            | Command::Repl { day } => std::slice::from_ref(day),
+            Command::RunAll { days, .. }
+            | Command::Verify { days, .. }
+            | Command::Bench { days, .. }
+            | Command::Fetch { days } => days,
+        }
+    }
+}
+
+pub fn parse(args: &[String]) -> Result<Cli, String> {
+    let mut args: Vec<String> = args.to_vec();
+    let log_level = Level::from_verbosity(take_verbosity(&mut args));
+    let example = take_example(&mut args)?;
+    let input = match take_value(&mut args, "--input")? {
+        Some(_) if example.is_some() => {
+            return Err(String::from("--input and --example can't be used together"))
+        }
+        Some(path) => InputSource::from_arg(&path),
+        None => example.map_or(InputSource::Default, InputSource::Example),
+    };
+    let format = match take_value(&mut args, "--format")? {
+        Some(f) => f.parse()?,
+        None => Format::Text,
+    };
+    let jobs = match take_value(&mut args, "--jobs")? {
+        Some(n) => parse_option(&n, "--jobs")?,
+        None => 1,
+    };
+    let mem = take_flag(&mut args, "--mem");
+    let timeout = match take_value(&mut args, "--timeout")? {
+        Some(s) => Some(parse_timeout(&s)?),
+        None => None,
+    };
+    let command = parse_command(args)?;
+    if jobs == 0 {
+        return Err(String::from("--jobs should be at least 1"));
+    }
+    if jobs > 1 && !matches!(command, Command::RunAll { .. } | Command::Verify { .. }) {
+        return Err(String::from(
+            "--jobs can only be used with run-all and verify",
+        ));
+    }
+    if format != Format::Text && !matches!(command, Command::Solve { .. } | Command::RunAll { .. })
+    {
+        return Err(String::from("--format can only be used when running days"));
+    }
+    if mem
+        && !matches!(
+            command,
+            Command::Solve { .. }
+                | Command::RunAll { .. }
+                | Command::Watch { .. }
+                | Command::Bench { .. }
+        )
+    {
+        return Err(String::from(
+            "--mem can only be used when running or benchmarking days",
+        ));
+    }
+    if timeout.is_some()
+        && !matches!(
+            command,
+            Command::Solve { .. }
+                | Command::RunAll { .. }
+                | Command::Verify { .. }
+                | Command::Watch { .. }
+                | Command::Submit { .. }
+        )
+    {
+        return Err(String::from(
+            "--timeout can only be used when running or verifying days",
+        ));
+    }
+    if input != InputSource::Default && matches!(command, Command::Fetch { .. }) {
+        return Err(String::from("fetch always saves to the input directory"));
+    }
+    if input != InputSource::Default && matches!(command, Command::Submit { .. }) {
+        return Err(String::from("submit always solves the saved puzzle input"));
+    }
+    if input == InputSource::Stdin && matches!(command, Command::Watch { .. }) {
+        return Err(String::from("watch needs an input file, not stdin"));
+    }
+    if input == InputSource::Stdin && matches!(command, Command::Repl { .. }) {
+        return Err(String::from(
+            "repl reads commands from stdin, so it needs an input file",
+        ));
+    }
+    if matches!(input, InputSource::File(_) | InputSource::Stdin) && command.days().len() != 1 {
+        return Err(String::from(
+            "--input can only be used when running a single day",
+        ));
+    }
+    if matches!(command, Command::Verify { record: true, .. }) && example.is_some() {
+        return Err(String::from("--record can't be used with --example"));
+    }
+    if matches!(command, Command::Bench { ref options, .. } if options.save) && example.is_some() {
+        return Err(String::from("--save can't be used with --example"));
+    }
+    if matches!(command, Command::Verify { record: true, .. })
+        && matches!(input, InputSource::File(_) | InputSource::Stdin)
+    {
+        return Err(String::from(
+            "--record only records answers for the saved puzzle input",
+        ));
+    }
+    if matches!(command, Command::Bench { ref options, .. } if options.save)
+        && matches!(input, InputSource::File(_) | InputSource::Stdin)
+    {
+        return Err(String::from(
+            "--save only saves a baseline for the saved puzzle input",
+        ));
+    }
+    Ok(Cli {
+        command,
+        input,
+        log_level,
+        format,
+        jobs,
+        mem,
+        timeout,
+    })
+}
+
+fn parse_command(mut args: Vec<String>) -> Result<Command, String> {
+    let command = match args.first().map(|s| s.as_str()) {
+        Some("verify") => {
+            args.remove(0);
+            let record = take_flag(&mut args, "--record");
+            let (days, part) = parse_selection(&positionals(&args)?, true)?;
+            Command::Verify { days, part, record }
+        }
+        Some("new") => match &positionals(&args[1..])?[..] {
+            [day] => Command::New {
+                day: parse_number(day, "day")?,
+            },
+            _ => return Err(String::from("new takes a single day")),
+        },
+        Some("watch") => match &positionals(&args[1..])?[..] {
+            [day, part] => Command::Watch {
+                day: parse_number(day, "day")?,
+                part: parse_number(part, "part")?,
+            },
+            _ => return Err(String::from("watch takes a day and a part")),
+        },
+        Some("fetch") => match parse_selection(&positionals(&args[1..])?, false)? {
+            (days, None) => Command::Fetch { days },
+            (_, Some(_)) => return Err(String::from("fetch takes days, not a part")),
+        },
+        Some("submit") => match &positionals(&args[1..])?[..] {
+            [day, part] => Command::Submit {
+                day: parse_number(day, "day")?,
+                part: parse_number(part, "part")?,
+            },
+            _ => return Err(String::from("submit takes a day and a part")),
+        },
+        Some("repl") => match &positionals(&args[1..])?[..] {
+            [day] => Command::Repl {
+                day: parse_number(day, "day")?,
+            },
+            _ => return Err(String::from("repl takes a single day")),
+        },
+        Some("bench") => {
+            args.remove(0);
+            let mut options = BenchOptions {
+                save: take_flag(&mut args, "--save"),
+                ..Default::default()
+            };
+            if let Some(runs) = take_value(&mut args, "--runs")? {
+                options.runs = parse_option(&runs, "--runs")?;
+            }
+            if let Some(warmup) = take_value(&mut args, "--warmup")? {
+                options.warmup = parse_option(&warmup, "--warmup")?;
+            }
+            if let Some(threshold) = take_value(&mut args, "--threshold")? {
+                options.threshold = parse_option(&threshold, "--threshold")?;
+            }
+            if options.runs == 0 {
+                return Err(String::from("--runs should be at least 1"));
+            }
+            let (days, part) = parse_selection(&positionals(&args)?, true)?;
+            Command::Bench {
+                days,
+                part,
+                options,
+            }
+        }
+        _ => {
+            let positionals = positionals(&args)?;
+            let is_single_day = positionals.first().is_some_and(|d| d.parse::<u8>().is_ok());
+            match parse_selection(&positionals, false)? {
+                (days, Some(part)) if is_single_day => Command::Solve { day: days[0], part },
+                (days, part) => Command::RunAll { days, part },
+            }
+        }
+    };
+    Ok(command)
+}
+
+/**
+ * Parses `[all | <day> | <from>-<to>] [part]`. An empty selection means every
+ * day when `default_all` is set.
+ */
+fn parse_selection(args: &[String], default_all: bool) -> Result<(Vec<u8>, Option<u8>), String> {
+    let selection = match args.first() {
+        Some(s) => s.as_str(),
+        None if default_all => "all",
+        None => return Err(String::from("Missing day argument")),
+    };
+    let part = match args.get(1) {
+        Some(p) => Some(parse_number(p, "part")?),
+        None => None,
+    };
+    if args.len() > 2 {
+        return Err(format!("Unexpected argument {:?}", args[2]));
+    }
+    if selection == "all" {
+        return Ok(((1..=25).collect(), part));
+    }
+    if let Some((from, to)) = selection.split_once('-') {
+        let from = parse_number(from, "day")?;
+        let to = parse_number(to, "day")?;
+        if from > to {
+            return Err(format!("Invalid day range {}", selection));
+        }
+        return Ok(((from..=to).collect(), part));
+    }
+    Ok((vec![parse_number(selection, "day")?], part))
+}
+
+fn parse_number(s: &str, what: &str) -> Result<u8, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("{} should be a number, got {:?}", what, s))
+}
+
+fn parse_option<T: std::str::FromStr>(s: &str, option: &str) -> Result<T, String> {
+    s.trim().parse().map_err(|_| format!("Invalid value for {}: {:?}", option, s))
+}
+
+// a positive number of seconds, which may be fractional
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = parse_option(s, "--timeout")?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(d) if !d.is_zero() => Ok(d),
+        _ => Err(format!(
+            "--timeout should be a positive number of seconds, got {:?}",
+            s
+        )),
+    }
+}
+
+// removes every -v, -vv, ... flag, returning the total number of v's
+fn take_verbosity(args: &mut Vec<String>) -> usize {
+    let is_verbose =
+        |a: &String| a.len() > 1 && a.starts_with('-') && a[1..].chars().all(|c| c == 'v');
+    let count = args.iter().filter(|a| is_verbose(a)).map(|a| a.len() - 1).sum();
+    args.retain(|a| !is_verbose(a));
+    count
+}
+
+// removes a boolean flag from the args, returning whether it was present
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != flag);
+    args.len() != before
+}
+
+// removes an option taking a value, given either as `--opt value` or `--opt=value`
+fn take_value(args: &mut Vec<String>, option: &str) -> Result<Option<String>, String> {
+    let prefix = format!("{}=", option);
+    for i in 0..args.len() {
+        if let Some(value) = args[i].strip_prefix(&prefix) {
+            let value = value.to_string();
+            args.remove(i);
+            return Ok(Some(value));
+        }
+        if args[i] == option {
+            if i + 1 >= args.len() {
+                return Err(format!("Missing value for {}", option));
+            }
+            let value = args.remove(i + 1);
+            args.remove(i);
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+// `--example` picks the first example, `--example=<n>` the nth
+fn take_example(args: &mut Vec<String>) -> Result<Option<usize>, String> {
+    let mut result = None;
+    if take_flag(args, "--example") {
+        result = Some(1);
+    }
+    if let Some(i) = args.iter().position(|a| a.starts_with("--example=")) {
+        let n: usize = parse_option(&args.remove(i)["--example=".len()..], "--example")?;
+        if n == 0 {
+            return Err(String::from("--example counts from 1"));
+        }
+        result = Some(n);
+    }
+    Ok(result)
+}
+
+// whatever is left after the known flags have been taken must be positional
+fn positionals(args: &[String]) -> Result<Vec<String>, String> {
+    match args.iter().find(|a| a.starts_with("--")) {
+        Some(flag) => Err(format!("Unknown option {}", flag)),
+        None => Ok(args.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn command(a: &[&str]) -> Result<Command, String> {
+        parse(&args(a)).map(|c| c.command)
+    }
+
+    #[test]
+    pub fn test_parse_single() {
+        assert_eq!(command(&["5", "2"]), Ok(Command::Solve { day: 5, part: 2 }));
+    }
+
+    #[test]
+    pub fn test_parse_all_and_ranges() {
+        assert_eq!(
+            command(&["all"]),
+            Ok(Command::RunAll {
+                days: (1..=25).collect(),
+                part: None
+            })
+        );
+        assert_eq!(
+            command(&["3-5", "1"]),
+            Ok(Command::RunAll {
+                days: vec![3, 4, 5],
+                part: Some(1)
+            })
+        );
+        assert_eq!(
+            command(&["7"]),
+            Ok(Command::RunAll {
+                days: vec![7],
+                part: None
+            })
+        );
+        assert_eq!(
+            command(&["7-7", "1"]),
+            Ok(Command::RunAll {
+                days: vec![7],
+                part: Some(1)
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_parse_verify() {
+        assert_eq!(
+            command(&["verify"]),
+            Ok(Command::Verify {
+                days: (1..=25).collect(),
+                part: None,
+                record: false
+            })
+        );
+        assert_eq!(
+            command(&["verify", "--record", "4", "2"]),
+            Ok(Command::Verify {
+                days: vec![4],
+                part: Some(2),
+                record: true
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_parse_bench() {
+        assert_eq!(
+            command(&[
+                "bench",
+                "14",
+                "2",
+                "--runs",
+                "5",
+                "--threshold=2.5",
+                "--save"
+            ]),
+            Ok(Command::Bench {
+                days: vec![14],
+                part: Some(2),
+                options: BenchOptions {
+                    runs: 5,
+                    warmup: 3,
+                    save: true,
+                    threshold: 2.5
+                }
+            })
+        );
+        assert!(command(&["bench", "--runs"]).is_err());
+        assert!(command(&["bench", "--runs", "0"]).is_err());
+        assert!(command(&["bench", "--warmup", "x"]).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_input() {
+        let cli = parse(&args(&[
+            "5", "1", "--input", "-",
+        ]))
+        .unwrap();
+        assert_eq!(cli.command, Command::Solve { day: 5, part: 1 });
+        assert_eq!(cli.input, InputSource::Stdin);
+        let cli = parse(&args(&[
+            "verify",
+            "5",
+            "--input=alt.txt",
+        ]))
+        .unwrap();
+        assert_eq!(cli.input, InputSource::from_arg("alt.txt"));
+        assert_eq!(parse(&args(&["5"])).unwrap().input, InputSource::Default);
+        assert!(parse(&args(&["all", "--input", "x.txt"])).is_err());
+        assert!(parse(&args(&["verify", "5", "--record", "--input", "x.txt"])).is_err());
+        assert!(parse(&args(&["bench", "5", "--save", "--input", "-"])).is_err());
+        assert!(parse(&args(&["bench", "5", "--input", "x.txt"])).is_ok());
+    }
+
+    #[test]
+    pub fn test_parse_example() {
+        let cli = parse(&args(&[
+            "all",
+            "--example",
+        ]))
+        .unwrap();
+        assert_eq!(cli.input, InputSource::Example(1));
+        let cli = parse(&args(&[
+            "10",
+            "2",
+            "--example=3",
+        ]))
+        .unwrap();
+        assert_eq!(cli.input, InputSource::Example(3));
+        assert!(parse(&args(&[
+            "10",
+            "--example=0"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "10",
+            "--example",
+            "--input",
+            "a.txt"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "verify",
+            "--example",
+            "--record"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "bench",
+            "10",
+            "--example",
+            "--save"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "bench",
+            "10",
+            "--example"
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    pub fn test_parse_verbosity() {
+        assert_eq!(parse(&args(&["5", "1"])).unwrap().log_level, Level::Quiet);
+        assert_eq!(
+            parse(&args(&["-v", "5", "1"])).unwrap().log_level,
+            Level::Info
+        );
+        assert_eq!(parse(&args(&["5", "-vv"])).unwrap().log_level, Level::Debug);
+        assert_eq!(
+            parse(&args(&["-v", "all", "-vv"])).unwrap().log_level,
+            Level::Trace
+        );
+    }
+
+    #[test]
+    pub fn test_parse_format() {
+        assert_eq!(parse(&args(&["5", "1"])).unwrap().format, Format::Text);
+        assert_eq!(
+            parse(&args(&["5", "1", "--format", "json"])).unwrap().format,
+            Format::Json
+        );
+        assert_eq!(
+            parse(&args(&[
+                "all",
+                "--format=csv"
+            ]))
+            .unwrap()
+            .format,
+            Format::Csv
+        );
+        assert!(parse(&args(&["all", "--format", "xml"])).is_err());
+        assert!(parse(&args(&["verify", "--format", "json"])).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_jobs() {
+        assert_eq!(parse(&args(&["all"])).unwrap().jobs, 1);
+        assert_eq!(parse(&args(&["all", "--jobs", "4"])).unwrap().jobs, 4);
+        assert_eq!(parse(&args(&["verify", "--jobs=2"])).unwrap().jobs, 2);
+        assert!(parse(&args(&["all", "--jobs", "0"])).is_err());
+        assert!(parse(&args(&["5", "1", "--jobs", "2"])).is_err());
+        assert!(parse(&args(&["bench", "--jobs", "2"])).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_mem() {
+        assert!(!parse(&args(&["all"])).unwrap().mem);
+        let cli = parse(&args(&[
+            "5", "1", "--mem",
+        ]))
+        .unwrap();
+        assert!(cli.mem);
+        assert_eq!(cli.command, Command::Solve { day: 5, part: 1 });
+        assert!(parse(&args(&["bench", "--mem"])).unwrap().mem);
+        assert!(parse(&args(&["verify", "--mem"])).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_timeout() {
+        assert_eq!(parse(&args(&["all"])).unwrap().timeout, None);
+        assert_eq!(
+            parse(&args(&[
+                "all",
+                "--timeout",
+                "2"
+            ]))
+            .unwrap()
+            .timeout,
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            parse(&args(&[
+                "verify",
+                "--timeout=0.5"
+            ]))
+            .unwrap()
+            .timeout,
+            Some(Duration::from_millis(500))
+        );
+        assert!(parse(&args(&[
+            "all",
+            "--timeout",
+            "0"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "all",
+            "--timeout",
+            "-1"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "bench",
+            "--timeout",
+            "1"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_parse_new() {
+        assert_eq!(command(&["new", "17"]), Ok(Command::New { day: 17 }));
+        assert!(command(&["new"]).is_err());
+        assert!(command(&["new", "17", "1"]).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_watch() {
+        assert_eq!(
+            command(&["watch", "13", "2"]),
+            Ok(Command::Watch { day: 13, part: 2 })
+        );
+        let cli = parse(&args(&[
+            "watch",
+            "13",
+            "2",
+            "--example=2",
+            "--timeout",
+            "1",
+        ]))
+        .unwrap();
+        assert_eq!(cli.input, InputSource::Example(2));
+        assert!(command(&["watch", "13"]).is_err());
+        assert!(parse(&args(&["watch", "13", "2", "--input", "-"])).is_err());
+        assert!(parse(&args(&["watch", "13", "2", "--format", "json"])).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_fetch() {
+        assert_eq!(
+            command(&["fetch", "5"]),
+            Ok(Command::Fetch { days: vec![5] })
+        );
+        assert_eq!(
+            command(&["fetch", "1-3"]),
+            Ok(Command::Fetch {
+                days: vec![1, 2, 3]
+            })
+        );
+        assert!(command(&["fetch"]).is_err());
+        assert!(command(&["fetch", "5", "1"]).is_err());
+        assert!(command(&[
+            "fetch",
+            "5",
+            "--example"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_parse_submit() {
+        assert_eq!(
+            command(&["submit", "5", "2"]),
+            Ok(Command::Submit { day: 5, part: 2 })
+        );
+        assert!(command(&["submit", "5"]).is_err());
+        assert!(command(&[
+            "submit",
+            "5",
+            "2",
+            "--example"
+        ])
+        .is_err());
+        assert!(command(&["submit", "5", "2", "--input", "x.txt"]).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_repl() {
+        assert_eq!(command(&["repl", "10"]), Ok(Command::Repl { day: 10 }));
+        assert!(command(&["repl", "10", "1"]).is_err());
+        assert!(command(&["repl", "10", "--input", "-"]).is_err());
+        assert!(command(&["repl", "10", "--mem"]).is_err());
+        assert_eq!(
+            parse(&[
+                "repl".into(),
+                "10".into(),
+                "--example=2".into()
+            ])
+            .map(|cli| cli.input),
+            Ok(InputSource::Example(2))
+        );
+    }
+
+    #[test]
+    pub fn test_parse_errors() {
+        assert!(command(&[]).is_err());
+        assert!(command(&["x", "1"]).is_err());
+        assert!(command(&["5-3"]).is_err());
+        assert!(command(&["5", "1", "2"]).is_err());
+        assert!(command(&["verify", "--bogus"]).is_err());
+    }
+}
@@ -1,23 +1,74 @@
 use std::error::Error;
 
-use regex::Regex;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
 
-pub fn part_1(v: &Vec<String>) -> u32 {
-    v.iter().map(get_calibration_value).sum()
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input)?.into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
 }
 
-pub fn part_2(v: &Vec<String>) -> u32 {
-    v.iter().map(get_calibration_value_v2).sum()
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+        part_1: Some("142"),
+        part_2: None,
+    },
+    Example {
+        input: "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+        part_1: None,
+        part_2: Some("281"),
+    },
+];
+
+pub fn part_1(v: &Input) -> Result<u32, ParseError> {
+    calibration_sum(v, get_calibration_value)
+}
+
+pub fn part_2(v: &Input) -> Result<u32, ParseError> {
+    calibration_sum(v, get_calibration_value_v2)
+}
+
+fn calibration_sum(v: &Input, value: fn(&str) -> Option<u32>) -> Result<u32, ParseError> {
+    v.lines()
+        .enumerate()
+        .map(|(i, s)| {
+            value(s).ok_or_else(|| ParseError::at(s, s, "expected a digit").on_line(i + 1))
+        })
+        .sum()
 }
 
-fn get_calibration_value(s: &String) -> u32 {
-    let re = Regex::new("([0-9]{1})").unwrap();
-    let d1 = re.find_iter(s).nth(0).unwrap();
-    let d2 = re.find_iter(s).last().unwrap();
-    // extract single char from each match
-    let d1 = d1.as_str().chars().nth(0).unwrap();
-    let d2 = d2.as_str().chars().nth(0).unwrap();
-    combine_digits(&(d1, d2))
+fn get_calibration_value(s: &str) -> Option<u32> {
+    let d1 = s.chars().find(char::is_ascii_digit)?;
+    let d2 = s.chars().rfind(char::is_ascii_digit)?;
+    Some(combine_digits(&(d1, d2)))
 }
 
 fn combine_digits(digits: &(char, char)) -> u32 {
@@ -30,10 +81,10 @@ fn combine_digits(digits: &(char, char)) -> u32 {
     }
 }
 
-fn get_calibration_value_v2(s: &String) -> u32 {
+fn get_calibration_value_v2(s: &str) -> Option<u32> {
     const BLANK: char = '-';
     let mut digits: (char, char) = (BLANK, BLANK);
-    digits.0 = get_first_digit_from_text(s.as_bytes()).unwrap();
+    digits.0 = get_first_digit_from_text(s.as_bytes())?;
     // same logic but working backwords through the string
     for pos in (0..s.len()).rev() {
         match get_first_digit_from_text(s[pos..].as_bytes()) {
@@ -45,7 +96,7 @@ fn get_calibration_value_v2(s: &String) -> u32 {
         }
     }
     let res = combine_digits(&digits);
-    res
+    Some(res)
 }
 
 fn get_first_digit_from_text(s: &[u8]) -> Option<char> {
@@ -97,13 +148,20 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/1.txt");
-        assert_eq!(part_1(&input), 54630);
+        let input = util::read_input("./input/1.txt");
+        assert_eq!(part_1(&input), Ok(54630));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/1.txt");
-        assert_eq!(part_2(&input), 54770);
+        let input = util::read_input("./input/1.txt");
+        assert_eq!(part_2(&input), Ok(54770));
+    }
+
+    #[test]
+    pub fn test_missing_digit() {
+        let err = part_1(&Input::from("1abc2\npqrstu")).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "pqrstu"));
+        assert_eq!(part_2(&Input::from("two1nine\nxyz")).unwrap_err().line, 2);
     }
 }
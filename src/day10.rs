@@ -1,12 +1,184 @@
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::repl::{self, Inspect};
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::maze::{Maze, MazePath};
+use crate::{info, trace};
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     vec,
 };
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    let maze = Maze::from(input);
-    let start = maze.find(&'S').expect("Should always have a starting point");
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input)?.into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn inspect(&self, input: &Input) -> Result<Option<Box<dyn Inspect>>, ParseError> {
+        Ok(Some(Box::new(Maze::from(input)?)))
+    }
+}
+
+impl Inspect for Maze {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("show grid", "print the maze"),
+            ("get <row> <col>", "show the pipe at a cell"),
+            ("start", "show where the animal starts"),
+            ("connected <row> <col>", "list the cells a pipe connects to"),
+            ("path", "follow the loop from the start and draw it"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let cell = || -> Result<(usize, usize), String> {
+            let (row, col) = (repl::arg(args, 0, "row")?, repl::arg(args, 1, "col")?);
+            match self.get(row, col) {
+                Some(_) => Ok((row, col)),
+                None => Err(format!("({}, {}) is outside the maze", row, col)),
+            }
+        };
+        let output = match (command, args) {
+            ("show", ["grid"]) => Ok(self.to_string()),
+            ("get", _) => cell().map(|(row, col)| self.get(row, col).unwrap().to_string()),
+            ("start", _) => self
+                .find(&'S')
+                .map(|(row, col)| format!("{} {}", row, col))
+                .ok_or_else(|| String::from("the maze has no start")),
+            ("connected", _) => cell().map(|(row, col)| {
+                let cells = self.get_connected_cells(row, col);
+                cells.iter().map(|(r, c)| format!("{} {}", r, c)).collect::<Vec<_>>().join("\n")
+            }),
+            ("path", _) => match self.find(&'S') {
+                Some(start) => {
+                    let path: HashSet<(usize, usize)> =
+                        HashSet::from_iter(get_pipe_path(self, &start).cells);
+                    // the maze with everything off the loop blanked out
+                    let grid = self
+                        .to_string()
+                        .lines()
+                        .enumerate()
+                        .map(|(row, line)| {
+                            line.chars()
+                                .enumerate()
+                                .map(|(col, c)| if path.contains(&(row, col)) { c } else { '.' })
+                                .collect::<String>()
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    Ok(format!(
+                        "loop length {}, farthest point {} steps away\n{}",
+                        path.len(),
+                        path.len() / 2,
+                        grid
+                    ))
+                }
+                None => Err(String::from("the maze has no start")),
+            },
+            _ => return None,
+        };
+        Some(output)
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        part_1: Some("4"),
+        part_2: None,
+    },
+    Example {
+        input: "\
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ",
+        part_1: Some("8"),
+        part_2: None,
+    },
+    Example {
+        input: "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........",
+        part_1: None,
+        part_2: Some("4"),
+    },
+    Example {
+        input: "\
+..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        part_1: None,
+        part_2: Some("4"),
+    },
+    Example {
+        input: "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...",
+        part_1: None,
+        part_2: Some("8"),
+    },
+    Example {
+        input: "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L",
+        part_1: None,
+        part_2: Some("10"),
+    },
+];
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let maze = Maze::from(input)?;
+    let start = find_start(&maze, input)?;
     // BFS searching tracking the distance of cells at each point
     let mut paths: VecDeque<MazePath> = VecDeque::new();
     paths.push_back(MazePath { cells: vec![start] });
@@ -42,13 +214,14 @@ pub fn part_1(input: &Vec<String>) -> u64 {
             None => break, // finished all path processing
         }
     }
-    println!("Total path evaluation operations: {:?}", path_op_count);
-    *distances.values().max().unwrap() as u64
+    info!("Total path evaluation operations: {:?}", path_op_count);
+    // the start is always in there, so the furthest point is at least 0 steps away
+    Ok(distances.values().copied().max().unwrap_or(0) as u64)
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let maze = Maze::from(input);
-    let start = maze.find(&'S').expect("Should always have a starting point");
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let maze = Maze::from(input)?;
+    let start = find_start(&maze, input)?;
     // DFS through the whole maze as a single path
     let pipe_path: MazePath = get_pipe_path(&maze, &start);
 
@@ -81,14 +254,24 @@ pub fn part_2(input: &Vec<String>) -> u64 {
             }
         }
         if north_south_intersections > (0, 0) {
-            println!("Ray: {:?}", ray);
-            println!("North/South intersections: {:?}", north_south_intersections);
+            trace!("Ray: {:?}", ray);
+            trace!("North/South intersections: {:?}", north_south_intersections);
         }
         if north_south_intersections.0.min(north_south_intersections.1) % 2 == 1 {
             count += 1;
         }
     }
-    count as u64
+    Ok(count as u64)
+}
+
+// a maze without an S is reported at the end of the input, as that's where we
+// gave up looking for it
+fn find_start(maze: &Maze, input: &Input) -> Result<(usize, usize), ParseError> {
+    maze.find(&'S').ok_or_else(|| {
+        let last = input.len().max(1);
+        let line = input.get(last - 1).unwrap_or("");
+        ParseError::at_end(line, "the maze has no starting point S").on_line(last)
+    })
 }
 
 // DFS until we reach the starting point again and return the path
@@ -162,15 +345,40 @@ mod tests {
     use super::*;
     // FIXME Move to integration tests
 
+    #[test]
+    pub fn test_inspect() {
+        let maze = Day10.inspect(&EXAMPLES[1].input()).unwrap().unwrap();
+        let run = |command: &str, args: &[&str]| maze.run(command, args).unwrap();
+        assert_eq!(run("show", &["grid"]), Ok(EXAMPLES[1].input.to_string()));
+        assert_eq!(run("start", &[]), Ok(String::from("2 0")));
+        assert_eq!(run("get", &["0", "2"]), Ok(String::from("F")));
+        assert!(run("get", &["0", "5"]).is_err());
+        assert_eq!(run("connected", &["0", "2"]), Ok(String::from("1 2\n0 3")));
+        let path = run("path", &[]).unwrap();
+        assert!(path.starts_with("loop length 16, farthest point 8 steps away\n..F7.\n.FJ|.\n"));
+        assert!(maze.run("seeds", &[]).is_none());
+        // a grid with no width can't be laid out, so it's rejected up front
+        assert!(Day10.inspect(&Input::from("")).is_err());
+        assert!(Day10.inspect(&Input::from("\n.S-7.")).is_err());
+    }
+
+    #[test]
+    pub fn test_missing_start() {
+        let input = Input::from("..F7.\n.FJ|.\n.L-J.");
+        let err = part_1(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+        assert_eq!(part_2(&input), Err(err));
+    }
+
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/10.txt");
-        assert_eq!(part_1(&input), 6931);
+        let input = util::read_input("./input/10.txt");
+        assert_eq!(part_1(&input), Ok(6931));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/10.txt");
-        assert_eq!(part_2(&input), 357);
+        let input = util::read_input("./input/10.txt");
+        assert_eq!(part_2(&input), Ok(357));
     }
 }
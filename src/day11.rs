@@ -1,13 +1,87 @@
+use crate::debug;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::repl::Inspect;
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::cosmic_map::CosmicMap;
 use itertools::Itertools;
 use std::collections::HashSet;
 
-pub fn part_1(input: &Vec<String>) -> u64 {
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input).into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input).into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn inspect(&self, input: &Input) -> Result<Option<Box<dyn Inspect>>, ParseError> {
+        Ok(Some(Box::new(CosmicMap::from(input))))
+    }
+}
+
+impl Inspect for CosmicMap {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("show grid", "print the map"),
+            ("empty", "list the rows and columns that expand"),
+            ("galaxies", "list the galaxies as row col, numbered from 1"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let output = match (command, args) {
+            ("show", ["grid"]) => self.to_string().trim_end().to_string(),
+            ("empty", _) => format!(
+                "rows: {}\ncols: {}",
+                self.get_empty_rows().iter().join(" "),
+                self.get_empty_cols().iter().join(" ")
+            ),
+            ("galaxies", _) => self
+                .get_galaxies(|c| *c == '#')
+                .iter()
+                .enumerate()
+                .map(|(i, (row, col))| format!("{:>3}  {} {}", i + 1, row, col))
+                .join("\n"),
+            _ => return None,
+        };
+        Some(Ok(output))
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....",
+    part_1: Some("374"),
+    part_2: Some("82000210"),
+}];
+
+pub fn part_1(input: &Input) -> u64 {
     let cosmic_map = CosmicMap::from(input);
     compute_distances_between_galaxies(&cosmic_map, 2)
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
+pub fn part_2(input: &Input) -> u64 {
     let cosmic_map = CosmicMap::from(input);
     compute_distances_between_galaxies(&cosmic_map, 1000000)
 }
@@ -15,7 +89,7 @@ pub fn part_2(input: &Vec<String>) -> u64 {
 fn compute_distances_between_galaxies(cosmic_map: &CosmicMap, scale: isize) -> u64 {
     let galaxies = cosmic_map.get_galaxies(|c| *c == '#');
     let galaxy_pairs = create_pairs(&galaxies);
-    println!("total galaxy pairs: {:?}", galaxy_pairs.len());
+    debug!("total galaxy pairs: {:?}", galaxy_pairs.len());
     // add <scale> to the row and col differences
     let mut sum: u64 = 0;
     let empty_rows = cosmic_map.get_empty_rows();
@@ -65,15 +139,30 @@ mod tests {
     use super::*;
     // FIXME Move to integration tests
 
+    #[test]
+    pub fn test_inspect() {
+        let map = Day11.inspect(&EXAMPLES[0].input()).unwrap().unwrap();
+        let run = |command: &str, args: &[&str]| map.run(command, args).unwrap();
+        assert_eq!(run("show", &["grid"]), Ok(EXAMPLES[0].input.to_string()));
+        assert_eq!(
+            run("empty", &[]),
+            Ok(String::from("rows: 3 7\ncols: 2 5 8"))
+        );
+        let galaxies = run("galaxies", &[]).unwrap();
+        assert_eq!(galaxies.lines().count(), 9);
+        assert_eq!(galaxies.lines().next(), Some("  1  0 3"));
+        assert!(map.run("path", &[]).is_none());
+    }
+
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/11.txt");
+        let input = util::read_input("./input/11.txt");
         assert_eq!(part_1(&input), 9536038);
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/11.txt");
+        let input = util::read_input("./input/11.txt");
         assert_eq!(part_2(&input), 447744640566);
     }
 }
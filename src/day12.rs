@@ -1,85 +1,180 @@
-use memoize::memoize;
-use regex::{self, Regex};
+use crate::cancel;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+use crate::util::memo::Memo;
+use crate::util::parse;
+use crate::{debug, trace};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input)?.into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1",
+    part_1: Some("21"),
+    // part 2 isn't solved yet, its answer is 525152
+    part_2: None,
+}];
 
 /**
  * we know backtracing brute force won't work for large scale.
  */
-pub fn part_1(input: &Vec<String>) -> u64 {
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let rows = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_row(line).map_err(|e| e.on_line(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut caches = Caches::default();
     let mut result: u64 = 0;
-    for line in input {
-        let parts: Vec<&str> = line.split(" ").collect();
-        let scheme = parts[0].clone().to_string();
-        let counts: Vec<usize> = parts[1]
-            .clone()
-            .to_string()
-            .split(",")
-            .map(|c| c.parse::<usize>().unwrap())
-            .collect();
-        let arrangements = get_arrangements(scheme, counts).unwrap();
+    for (i, (springs, counts)) in rows.iter().enumerate() {
+        let arrangements = get_arrangements(springs, counts, &mut caches).ok_or_else(|| {
+            ParseError::at(
+                &input[i],
+                springs,
+                "too short to fit the groups of broken springs",
+            )
+            .on_line(i + 1)
+        })?;
         result += arrangements;
-        println!("{:?} = {:?}", line, arrangements);
+        debug!("{:?} {:?} = {:?}", springs, counts, arrangements);
     }
-    return result;
+    debug!(
+        "arrangements cache: {}, validations cache: {}",
+        caches.arrangements, caches.validations
+    );
+    Ok(result)
+}
+
+// a "<springs> <counts>" row like "???.### 1,1,3"
+fn parse_row(line: &str) -> Result<(&str, Vec<usize>), ParseError> {
+    let (springs, counts) = parse::split_once(line, line, " ", "expected <springs> <counts>")?;
+    Ok((springs, parse::separated_numbers(line, counts, ',')?))
+}
+
+pub fn part_2(_input: &Input) -> Answer {
+    Answer::Unsolved
 }
 
-pub fn part_2(_input: &Vec<String>) -> u64 {
-    0
+// the memoized results for one run, keyed on slices of the rows
+#[derive(Default)]
+struct Caches<'a> {
+    arrangements: Memo<(&'a str, &'a [usize]), u64>,
+    validations: Memo<(String, usize), u64>,
+}
+
+// None when a group of broken springs runs off the end of the row
+fn get_arrangements<'a>(input: &'a str, nums: &'a [usize], caches: &mut Caches<'a>) -> Option<u64> {
+    if let Some(result) = caches.arrangements.get(&(input, nums)) {
+        return Some(result);
+    }
+    let result = count_arrangements(input, nums, caches)?;
+    Some(caches.arrangements.insert((input, nums), result))
+}
+
+// the first run of springs that are any of `springs`, and where it starts
+fn first_run<'a>(input: &'a str, springs: &[char]) -> Option<(usize, &'a str)> {
+    let start = input.find(springs)?;
+    let run = input[start..].split(|c| !springs.contains(&c)).next().unwrap_or("");
+    Some((start, &input[start..start + run.len()]))
 }
 
 // followed https://www.youtube.com/watch?v=g3Ms5e7Jdqo for explanation
-#[memoize]
-fn get_arrangements(input: String, nums: Vec<usize>) -> Result<u64, regex::Error> {
-    println!("input: {:?} {:?}", input, nums);
+fn count_arrangements<'a>(
+    input: &'a str,
+    nums: &'a [usize],
+    caches: &mut Caches<'a>,
+) -> Option<u64> {
+    trace!("input: {:?} {:?}", input, nums);
     if nums.len() == 0 {
         if !input.contains('#') {
-            return Ok(1);
+            return Some(1);
         } else {
-            return Ok(0);
+            return Some(0);
         }
     }
-    let broken_re = Regex::new("([#?])+")?;
     let mut result = 0;
     let expected_springs = nums[0];
-    match broken_re.find(&input) {
-        Some(m) => {
-            let arrangements = validate_arrangements(m.as_str().to_string(), expected_springs);
-            println!("\t{:?} arrangements: {:?}", m, arrangements);
+    match first_run(input, &['#', '?']) {
+        Some((start, run)) => {
+            let arrangements =
+                validate_arrangements(run.to_string(), expected_springs, &mut caches.validations);
+            trace!("\t{:?} arrangements: {:?}", run, arrangements);
             result += arrangements;
-            result += get_arrangements(
-                input[m.range().start + expected_springs..].to_string(),
-                nums[1..].to_vec(),
-            )
-            .unwrap();
+            let rest = input.get(start + expected_springs..)?;
+            result += get_arrangements(rest, &nums[1..], caches)?;
         }
         None => {
-            return Ok(result);
+            return Some(result);
         }
     }
 
-    return Ok(result);
+    Some(result)
 }
 
 // this will probably be needed in part 2
-#[memoize]
-fn validate_arrangements(input: String, num: usize) -> u64 {
-    // println!("validate arrangement input: {:?} , {:?}",input,num);
-    let broken_re = Regex::new("([#])+").unwrap();
+fn validate_arrangements(input: String, num: usize, memo: &mut Memo<(String, usize), u64>) -> u64 {
+    let key = (input, num);
+    if let Some(result) = memo.get(&key) {
+        return result;
+    }
+    let result = count_valid_arrangements(&key.0, num, memo);
+    memo.insert(key, result)
+}
+
+fn count_valid_arrangements(input: &str, num: usize, memo: &mut Memo<(String, usize), u64>) -> u64 {
+    cancel::checkpoint();
     match input.find("?") {
-        None => {
-            match broken_re.find(&input) {
-                Some(m) if m.len() == num => {
-                    // println!("Found a valid combination {:?}", input);
-                    return 1;
-                }
-                _ => {
-                    // println!("Found a invalid combination {:?}", input);
-                    return 0;
-                }
-            }
-        }
-        Some(_i) => {
-            return validate_arrangements(input.replacen("?", "#", 1), num)
-                + validate_arrangements(input.replacen("?", ".", 1), num);
+        None => match first_run(input, &['#']) {
+            Some((_, run)) if run.len() == num => 1,
+            _ => 0,
+        },
+        Some(_) => {
+            validate_arrangements(input.replacen("?", "#", 1), num, memo)
+                + validate_arrangements(input.replacen("?", ".", 1), num, memo)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_first_run() {
+        let springs = ['#', '?'];
+        assert_eq!(first_run(".??..#?.", &springs), Some((1, "??")));
+        assert_eq!(first_run(".??..#?.", &['#']), Some((5, "#")));
+        assert_eq!(first_run("...", &springs), None);
+        assert_eq!(first_run("..##", &springs), Some((2, "##")));
+    }
+
+    #[test]
+    pub fn test_group_past_the_end() {
+        let err = part_1(&Input::from("???.### 1,1,3\n.#? 4")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, ".#?"));
+    }
+}
@@ -1,20 +1,64 @@
-use crate::util::{partiiton_on_empty, print_block};
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+use crate::{debug, info};
 
-pub fn part_1(input: &Vec<String>) -> u64 {
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input).into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input).into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#",
+    part_1: Some("405"),
+    part_2: Some("400"),
+}];
+
+pub fn part_1(input: &Input) -> u64 {
     let mut result = 0;
     let mut no_reflection_cnt = 0;
-    let patches = partiiton_on_empty(input);
-    for patch in patches {
+    for block in input.blocks() {
+        let patch: Vec<&str> = block.lines().collect();
         result += get_reflection_summary(&patch, usize::MAX).1 as u64;
     }
-    println!("Total patches with no reflection: {:?}", no_reflection_cnt);
+    info!("Total patches with no reflection: {:?}", no_reflection_cnt);
     result
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
+pub fn part_2(input: &Input) -> u64 {
     let mut result = 0;
-    let patches = partiiton_on_empty(input);
-    for (i, patch) in patches.iter().enumerate() {
+    for block in input.blocks() {
+        let patch: Vec<&str> = block.lines().collect();
         let original_reflection = get_reflection_summary(&patch, usize::MAX);
         let smudge_fixes = get_possible_smudges(&patch);
         let mut fixed = false;
@@ -28,16 +72,18 @@ pub fn part_2(input: &Vec<String>) -> u64 {
             }
         }
         if !fixed {
-            println!("No fix found at index {}!", i);
-            print_block(&patch);
-            println!("-----------------")
+            debug!(
+                "No fix found for the patch on line {}!\n{}\n-----------------",
+                block.line_number(),
+                patch.join("\n")
+            );
         }
     }
     result as u64
 }
 
 // generate possible smudges for a particular patch
-fn get_possible_smudges(patch: &Vec<String>) -> Vec<Vec<String>> {
+fn get_possible_smudges(patch: &[&str]) -> Vec<Vec<String>> {
     let mut result: Vec<Vec<String>> = Vec::new();
     // rebuild the patch swapping one char at a time. Painstakingly slow
     // but gets around the borrow checker for now
@@ -61,7 +107,7 @@ fn get_possible_smudges(patch: &Vec<String>) -> Vec<Vec<String>> {
     return result;
 }
 
-fn get_reflection_summary(patch: &Vec<String>, exclude: usize) -> (usize, usize) {
+fn get_reflection_summary<S: AsRef<str>>(patch: &[S], exclude: usize) -> (usize, usize) {
     let mut vertical = match get_vertical_line_reflection(&patch, 0, 1, exclude) {
         Some(p) => p,
         None => 0,
@@ -92,13 +138,13 @@ fn get_reflection_summary(patch: &Vec<String>, exclude: usize) -> (usize, usize)
     return (vertical, vertical);
 }
 
-fn get_vertical_line_reflection(
-    patch: &Vec<String>,
+fn get_vertical_line_reflection<S: AsRef<str>>(
+    patch: &[S],
     left_straddle: usize,
     right_straddle: usize,
     exclude: usize,
 ) -> Option<usize> {
-    let patch_width = patch[0].len();
+    let patch_width = patch[0].as_ref().len();
     if right_straddle >= patch_width {
         return None;
     }
@@ -109,8 +155,8 @@ fn get_vertical_line_reflection(
             break;
         }
         for line in patch {
-            let c1 = line.chars().nth(left_straddle - distance).unwrap();
-            let c2 = line.chars().nth(right_straddle + distance).unwrap();
+            let c1 = line.as_ref().chars().nth(left_straddle - distance).unwrap();
+            let c2 = line.as_ref().chars().nth(right_straddle + distance).unwrap();
             if c1 != c2 {
                 is_reflection = false;
                 continue 'dist;
@@ -123,8 +169,8 @@ fn get_vertical_line_reflection(
     return get_vertical_line_reflection(patch, left_straddle + 1, right_straddle + 1, exclude);
 }
 
-fn get_horizontal_line_reflection(
-    patch: &Vec<String>,
+fn get_horizontal_line_reflection<S: AsRef<str>>(
+    patch: &[S],
     top_straddle: usize,
     bottomn_straddle: usize,
     exclude: usize,
@@ -139,8 +185,8 @@ fn get_horizontal_line_reflection(
         if top_straddle < distance || distance + bottomn_straddle >= patch_len {
             break;
         }
-        let top = patch.get(top_straddle - distance).unwrap();
-        let bottom = patch.get(bottomn_straddle + distance).unwrap();
+        let top = patch[top_straddle - distance].as_ref();
+        let bottom = patch[bottomn_straddle + distance].as_ref();
         if top != bottom {
             is_reflection = false;
             break;
@@ -152,7 +198,7 @@ fn get_horizontal_line_reflection(
     return get_horizontal_line_reflection(patch, top_straddle + 1, bottomn_straddle + 1, exclude);
 }
 
-fn get_all_horizontal_reflection(patch: &Vec<String>) -> Vec<usize> {
+fn get_all_horizontal_reflection(patch: &[&str]) -> Vec<usize> {
     let mut result = Vec::new();
     for i in 0..patch.len() {
         match get_horizontal_line_reflection(patch, 0, 1, i) {
@@ -163,7 +209,7 @@ fn get_all_horizontal_reflection(patch: &Vec<String>) -> Vec<usize> {
     return result;
 }
 
-fn get_all_vertical_reflection(patch: &Vec<String>) -> Vec<usize> {
+fn get_all_vertical_reflection(patch: &[&str]) -> Vec<usize> {
     let mut result = Vec::new();
     for i in 0..patch.len() {
         match get_vertical_line_reflection(patch, 0, 1, i) {
@@ -183,13 +229,13 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/13.txt");
+        let input = util::read_input("./input/13.txt");
         assert_eq!(part_1(&input), 35691);
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/13.txt");
+        let input = util::read_input("./input/13.txt");
         assert_eq!(part_2(&input), 39037);
     }
 }
@@ -1,14 +1,52 @@
-use std::{fmt::Debug, collections::HashSet, time::Instant, ops::Index};
+use std::{fmt::Debug, time::Instant};
 
-use itertools::Itertools;
-use memoize::memoize;
+use crate::cancel;
+use crate::error::ParseError;
+use crate::info;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+use crate::util;
 
-use crate::util::print_block;
+pub struct Day14;
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    let mut dish = Dish::from(input.clone());
+impl Solution for Day14 {
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....",
+    part_1: Some("136"),
+    part_2: Some("64"),
+}];
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let mut dish = Dish::from(input)?;
     dish = tilt_north(dish);
-    dish.get_load() as u64
+    Ok(dish.get_load() as u64)
 }
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
@@ -17,28 +55,21 @@ struct Dish {
     cells: Vec<u8>,
 }
 
-impl From<Vec<String>> for Dish {
-    fn from(value: Vec<String>) -> Self {
-        Dish {
-            row_width: value[0].len(),
-            cells: value.iter().flat_map(|s| s.chars().map(|c| c as u8)).collect(),
-        }
-    }
-}
-
 impl Dish {
-    // helper to print debugging info
-    pub fn print_debug_layout(&self) {
-        let cells: Vec<char> = self.cells.iter().map(|b| *b as char).collect();
-        let mut cnt = 0;
-        for c in cells {
-            print!("{}", c);
-            cnt += 1;
-            if cnt >= self.row_width {
-                println!("");
-                cnt = 0;
-            }
+    // the cells are laid out by row width, so every row has to be as wide as the first
+    pub fn from(input: &Input) -> Result<Dish, ParseError> {
+        let row_width = match input.get(0) {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(ParseError::at_end("", "expected a grid of rocks").on_line(1)),
+        };
+        if let Some((i, row)) = input.lines().enumerate().find(|(_, r)| r.len() != row_width) {
+            let message = format!("expected a row {} wide like the first", row_width);
+            return Err(ParseError::at_end(row, message).on_line(i + 1));
         }
+        Ok(Dish {
+            row_width: row_width,
+            cells: input.lines().flat_map(|s| s.bytes()).collect(),
+        })
     }
 
     pub fn get_load(&self) -> usize {
@@ -55,36 +86,23 @@ impl Dish {
     }
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let mut dish: Dish = Dish::from(input.clone());
-    let mut cache: HashSet<Dish> = HashSet::new();
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let dish: Dish = Dish::from(input)?;
     let total_iterations = 1_000_000_000;
-    let mut cycle_size = 0;
-    let mut numerator = 0;
     let start = Instant::now();
-    // worst case, if we never had a cycle...
-    let mut hist: Vec<Dish> = Vec::new();
-    let mut last_idx = 0;
-    let mut finished_loops = 0;
-    for i in 0..total_iterations{ 
-        println!("Processed {}, elapsed: {}ms", i, start.elapsed().as_millis()); 
-        finished_loops = i;
-        dish = cycle(dish);
-        let cloned = dish.clone();
-        if !cache.contains(&cloned){
-            cache.insert(cloned);
-            hist.push(dish.clone());
-        } else {
-            println!("Found a cycle at {}, cycle size {}", i, cache.len());
-            last_idx = hist.iter().find_position(|d| **d == dish).unwrap().0;
-            break;
-        }
-    }
-    let result_idx = (last_idx) + (total_iterations-1-finished_loops) % (finished_loops - last_idx);
-    hist.get(result_idx).unwrap().get_load() as u64
+    // the dish settles in to a loop long before the last spin, so skip round it
+    let dish = util::cycle::state_after(
+        dish,
+        |d| {
+            cancel::checkpoint();
+            cycle(d.clone())
+        },
+        total_iterations,
+    );
+    info!("Spun the dish after {}ms", start.elapsed().as_millis());
+    Ok(dish.get_load() as u64)
 }
 
-#[memoize]
 fn cycle(dish: Dish) -> Dish {
     tilt_east(tilt_south(tilt_west(tilt_north(dish))))
 }
@@ -112,7 +130,7 @@ fn tilt_north(dish: Dish) -> Dish {
             }
         }
     }
-    if swapped { 
+    if swapped {
         return tilt_north(dish);
     }
     dish
@@ -141,7 +159,7 @@ fn tilt_south(dish: Dish) -> Dish {
             }
         }
     }
-    if swapped { 
+    if swapped {
         return tilt_south(dish);
     }
     dish
@@ -199,3 +217,15 @@ fn tilt_west(dish: Dish) -> Dish {
     }
     dish
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_malformed_dish() {
+        assert_eq!(part_1(&Input::from("")).unwrap_err().line, 1);
+        let err = part_2(&Input::from("O..\n.#.\n#.")).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+}
@@ -1,24 +1,44 @@
-use regex::Regex;
+use crate::debug;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+use crate::util::parse;
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    // we know it's one long line
-    input
-        .get(0)
-        .expect("should have one input line")
-        .split(",")
-        .into_iter()
-        .map(|i| holiday_hash(&i.chars().collect::<Vec<char>>(), 0))
-        .sum::<u64>()
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input)?.into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let instructions: Vec<(String, u64, LensOp, u64)> = input //convert input in to a list of instructions
-        .get(0)
-        .expect("should have one input line")
-        .split(",")
-        // convert each instruction in to (label, op, optional length)
-        .map(get_instruction)
-        .collect();
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7",
+    part_1: Some("1320"),
+    part_2: Some("145"),
+}];
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let steps = parse_steps(input, |_, step| Ok(holiday_hash(step.as_bytes(), 0)))?;
+    Ok(steps.into_iter().sum::<u64>())
+}
+
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    // convert each instruction in to (label, op, optional length)
+    let instructions: Vec<(&str, u64, LensOp, u64)> = parse_steps(input, get_instruction)?;
     // we have a maximum of 256 cells
     let mut boxes: Vec<Vec<Lense>> = Vec::new();
     for _ in 0..256 {
@@ -69,7 +89,7 @@ pub fn part_2(input: &Vec<String>) -> u64 {
     let mut total_focusing_power: u64 = 0;
     for (i, b) in boxes.iter().enumerate() {
         if b.len() > 0 {
-            println!("Box {:?} {:?}", i, b);
+            debug!("Box {:?} {:?}", i, b);
         }
         let box_nr = (i + 1) as u64;
         for (j, lense) in b.iter().enumerate() {
@@ -78,8 +98,20 @@ pub fn part_2(input: &Vec<String>) -> u64 {
             total_focusing_power += (box_nr * lense_slot * focal) as u64;
         }
     }
-    total_focusing_power
+    Ok(total_focusing_power)
+}
+
+// we know it's one long line of comma separated steps
+fn parse_steps<'a, T>(
+    input: &'a Input,
+    step: impl Fn(&'a str, &'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let line = input
+        .get(0)
+        .ok_or_else(|| ParseError::at_end("", "expected a line of steps").on_line(1))?;
+    parse::separated(line, line, ',', |s| step(line, s)).map_err(|e| e.on_line(1))
 }
+
 #[derive(Debug)]
 enum LensOp {
     REMOVE,
@@ -87,27 +119,27 @@ enum LensOp {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Lense {
-    lbl: String,
+struct Lense<'a> {
+    lbl: &'a str,
     focal_len: u64,
 }
 
-fn get_instruction(i: &str) -> (String, u64, LensOp, u64) {
-    let re = Regex::new("^([a-zA-Z]+)([=-]){1}([0-9]?)").unwrap();
-    match re.captures(i).map(|c| c.extract::<3>()) {
-        Some((_, [label, op, lense])) => {
-            let lbl_hash = holiday_hash(&String::from(label).chars().collect(), 0);
-            let op = match op {
-                "=" => LensOp::ADD,
-                _ => LensOp::REMOVE,
-            };
-            let lense = lense.parse::<u64>().unwrap_or_default();
-            return (String::from(label), lbl_hash, op, lense);
-        }
-        None => panic!("Pattern did not match"),
+// `line` is the whole sequence, to locate errors in the step
+fn get_instruction<'a>(line: &str, i: &'a str) -> Result<(&'a str, u64, LensOp, u64), ParseError> {
+    let (label, op, lense) = if let Some((label, lense)) = i.split_once('=') {
+        (label, LensOp::ADD, parse::number(line, lense)?)
+    } else if let Some(label) = i.strip_suffix('-') {
+        (label, LensOp::REMOVE, 0)
+    } else {
+        return Err(ParseError::at(line, i, "expected a step like rn=1 or cm-"));
+    };
+    if label.is_empty() || !label.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return Err(ParseError::at(line, label, "expected a label of letters"));
     }
+    let lbl_hash = holiday_hash(label.as_bytes(), 0);
+    Ok((label, lbl_hash, op, lense))
 }
-fn holiday_hash(input: &Vec<char>, result: u64) -> u64 {
+fn holiday_hash(input: &[u8], result: u64) -> u64 {
     match input.get(0) {
         None => {
             return result;
@@ -116,7 +148,21 @@ fn holiday_hash(input: &Vec<char>, result: u64) -> u64 {
             let mut updated_result = result + *c as u64;
             updated_result *= 17 as u64;
             updated_result %= 256;
-            return holiday_hash(&input[1..].to_vec(), updated_result);
+            return holiday_hash(&input[1..], updated_result);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_steps() {
+        let err = part_2(&Input::from("rn=1,cm-,qp=x")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 13, "x"));
+        let err = part_2(&Input::from("rn=1,cm,qp=3")).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "cm"));
+        assert!(part_1(&Input::from("rn=1,")).is_err());
+    }
+}
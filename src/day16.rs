@@ -1,7 +1,27 @@
-pub fn part_1(_input: &Vec<String>) -> u64 {
-    0
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Solution};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input))
+    }
+}
+
+pub fn part_1(_input: &Input) -> Answer {
+    Answer::Unsolved
 }
 
-pub fn part_2(_input: &Vec<String>) -> u64 {
-    0
+pub fn part_2(_input: &Input) -> Answer {
+    Answer::Unsolved
 }
@@ -1,60 +1,101 @@
+use crate::error::{self, ParseError};
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::game::{Game, GameCubeCount, Round};
+use crate::util::parse;
 
-pub fn part_1(input: &Vec<String>) -> u32 {
-    input
-        .into_iter()
-        .map(build_game)
-        .filter(is_valid_game)
-        .map(|g: Game| g.id)
-        .sum::<u32>()
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
 }
 
-pub fn part_2(input: &Vec<String>) -> u32 {
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    part_1: Some("8"),
+    part_2: Some("2286"),
+}];
+
+pub fn part_1(input: &Input) -> Result<u32, ParseError> {
+    let games = build_games(input)?;
+    Ok(games.into_iter().filter(is_valid_game).map(|g: Game| g.id).sum::<u32>())
+}
+
+pub fn part_2(input: &Input) -> Result<u32, ParseError> {
+    let games = build_games(input)?;
+    Ok(games.into_iter().map(min_cubes_required).map(get_power).sum::<u32>())
+}
+
+fn build_games(input: &Input) -> Result<Vec<Game>, ParseError> {
     input
-        .into_iter()
-        .map(build_game)
-        .map(min_cubes_required)
-        .map(get_power)
-        .sum::<u32>()
+        .lines()
+        .enumerate()
+        .map(|(i, line)| build_game(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
 }
 
-fn build_game(input: &String) -> Game {
-    let input_parts: Vec<&str> = input.split(":").collect();
-    let game_id_parts: Vec<&str> = input_parts.get(0).unwrap().split_ascii_whitespace().collect();
-    let game_id: u32 = game_id_parts.get(1).unwrap().parse().unwrap();
-    let raw_rounds: Vec<&str> = input_parts.get(1).unwrap().split(";").collect();
-    let rounds: Vec<Round> = raw_rounds.iter().map(build_round).collect();
+fn build_game(input: &str) -> Result<Game, ParseError> {
+    let (game, raw_rounds) = parse::record(input, ';')?;
+    let game_id_parts: Vec<&str> = game.split_ascii_whitespace().collect();
+    let game_id: u32 = match game_id_parts[..] {
+        ["Game", id] => error::parse_number(input, id)?,
+        _ => return Err(ParseError::at(input, game, "expected Game <id>")),
+    };
+    let rounds: Vec<Round> = raw_rounds
+        .into_iter()
+        .map(|r| build_round(input, r))
+        .collect::<Result<_, _>>()?;
 
     let result = Game {
         id: game_id,
         rounds: rounds,
     };
-    result
+    Ok(result)
 }
 
-fn build_round(input: &&str) -> Round {
+// `line` is the whole game, to locate errors in the round
+fn build_round(line: &str, input: &str) -> Result<Round, ParseError> {
     let mut result: Round = Round {
         red_count: 0,
         green_count: 0,
         blue_count: 0,
     };
-    let round_parts = input.split(",");
-    for part in round_parts {
-        let dice_and_count: Vec<&str> = part.split_ascii_whitespace().collect();
-        match dice_and_count.get(1).unwrap().to_string().as_str() {
+    for part in parse::separated(line, input, ',', Ok)? {
+        let (count, colour) = parse::split_once(line, part, " ", "expected <count> <colour>")?;
+        let colour = colour.trim();
+        match colour {
             "blue" => {
-                result.blue_count = dice_and_count.get(0).unwrap().parse().unwrap();
+                result.blue_count = error::parse_number(line, count)?;
             }
             "red" => {
-                result.red_count = dice_and_count.get(0).unwrap().parse().unwrap();
+                result.red_count = error::parse_number(line, count)?;
             }
             "green" => {
-                result.green_count = dice_and_count.get(0).unwrap().parse().unwrap();
+                result.green_count = error::parse_number(line, count)?;
             }
-            _ => {}
+            _ => return Err(ParseError::at(line, colour, "unknown colour")),
         }
     }
-    return result;
+    return Ok(result);
 }
 
 fn is_valid_game(g: &Game) -> bool {
@@ -104,13 +145,23 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/2.txt");
-        assert_eq!(part_1(&input), 2512);
+        let input = util::read_input("./input/2.txt");
+        assert_eq!(part_1(&input), Ok(2512));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/2.txt");
-        assert_eq!(part_2(&input), 67335);
+        let input = util::read_input("./input/2.txt");
+        assert_eq!(part_2(&input), Ok(67335));
+    }
+
+    #[test]
+    pub fn test_build_game_errors() {
+        let err = build_game("Game 3: 1 red, x blue").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (16, "x"));
+        let err = build_game("Game 3: 1 red; 2 purple").unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (18, "unknown colour"));
+        let err = build_game("Game 3 1 red").unwrap_err();
+        assert_eq!(err.column, 13);
     }
 }
@@ -1,6 +1,45 @@
-use crate::structs::grid::{from, Grid, GridCoordinate};
+use crate::error::ParseError;
+use crate::input::{Grid, Input};
+use crate::solution::{Answer, Example, Solution};
+use crate::structs::grid::GridCoordinate;
 use std::collections::HashSet;
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input).into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input).into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+    part_1: Some("4361"),
+    part_2: Some("467835"),
+}];
+
 // consts
 const ADJ_CELL_DELTAS: [GridCoordinate; 8] = [
     GridCoordinate(1, -1),
@@ -15,10 +54,10 @@ const ADJ_CELL_DELTAS: [GridCoordinate; 8] = [
 const BLANK: char = '.';
 const GEAR: char = '*';
 
-pub fn part_1(input: &Vec<String>) -> u32 {
+pub fn part_1(input: &Input) -> u32 {
     // build a grid from the input and
     // scan grid for indices of symbols
-    let grid = from(input);
+    let grid = input.grid();
     let symbol_coordinates = find(&grid, |c| c != &BLANK && c.to_digit(10) == None);
 
     let mut partial_part_number: HashSet<GridCoordinate> = HashSet::new();
@@ -56,10 +95,10 @@ pub fn part_1(input: &Vec<String>) -> u32 {
     result
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
+pub fn part_2(input: &Input) -> u64 {
     // build a grid from the input and
     // scan grid for indices of symbols
-    let grid = from(input);
+    let grid = input.grid();
     let gear_coordinates: Vec<GridCoordinate> = find(&grid, |c| c == &GEAR);
 
     let mut result: u64 = 0;
@@ -151,27 +190,17 @@ fn get_full_number_coordinates(partial: GridCoordinate, grid: &Grid) -> Vec<Grid
  * returns true
  */
 fn find(grid: &Grid, test: fn(&char) -> bool) -> Vec<GridCoordinate> {
-    let mut symbol_coordinates: Vec<GridCoordinate> = Vec::new();
-
-    for (r, row) in grid.rows.iter().enumerate() {
-        for (c, col) in row.iter().enumerate() {
-            if test(col) {
-                symbol_coordinates.push(GridCoordinate(r as i32, c as i32));
-            }
-        }
-    }
-    symbol_coordinates
+    grid.cells()
+        .filter(|(_, c)| test(c))
+        .map(|((r, c), _)| GridCoordinate(r as i32, c as i32))
+        .collect()
 }
 
-fn get<'a>(g: &'a Grid, c: &GridCoordinate) -> Option<&'a char> {
-    match g.rows.get(c.0 as usize) {
-        Some(r) => {
-            return r.get(c.1 as usize);
-        }
-        None => {
-            return None;
-        }
-    };
+fn get(g: &Grid, c: &GridCoordinate) -> Option<char> {
+    if c.0 < 0 || c.1 < 0 {
+        return None;
+    }
+    g.get(c.0 as usize, c.1 as usize)
 }
 
 #[cfg(test)]
@@ -183,13 +212,13 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/3.txt");
+        let input = util::read_input("./input/3.txt");
         assert_eq!(part_1(&input), 521515);
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/3.txt");
+        let input = util::read_input("./input/3.txt");
         assert_eq!(part_2(&input), 69527306);
     }
 }
@@ -1,13 +1,49 @@
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::scratchcard::ScratchCard;
+use crate::util::parse;
 use std::collections::HashSet;
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    input.into_iter().map(to_card).map(get_score).sum()
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    part_1: Some("13"),
+    part_2: Some("30"),
+}];
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    Ok(to_cards(input)?.into_iter().map(get_score).sum())
+}
+
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
     let winning_number_counts: Vec<u8> =
-        input.into_iter().map(to_card).map(count_winning_numbers).collect();
+        to_cards(input)?.into_iter().map(count_winning_numbers).collect();
 
     let mut card_counts: Vec<u64> = Vec::with_capacity(winning_number_counts.len());
     for _ in 0..winning_number_counts.len() {
@@ -24,7 +60,7 @@ pub fn part_2(input: &Vec<String>) -> u64 {
             }
         }
     }
-    card_counts.iter().sum::<u64>()
+    Ok(card_counts.iter().sum::<u64>())
 }
 
 fn count_winning_numbers(c: ScratchCard) -> u8 {
@@ -40,18 +76,22 @@ fn get_score(c: ScratchCard) -> u64 {
     }
 }
 
-fn to_card(s: &String) -> ScratchCard {
-    let parts: Vec<Vec<u8>> =
-        s.split(":").into_iter().nth(1).unwrap().split("|").map(to_u8_vec).collect();
-
-    ScratchCard {
-        numbers_you_have: parts.get(0).unwrap().to_vec(),
-        winning_numbers: parts.get(1).unwrap().to_vec(),
-    }
+fn to_cards(input: &Input) -> Result<Vec<ScratchCard>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| to_card(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
 }
 
-fn to_u8_vec(s: &str) -> Vec<u8> {
-    s.trim().split_ascii_whitespace().map(|a| a.parse().unwrap()).collect()
+fn to_card(s: &str) -> Result<ScratchCard, ParseError> {
+    let (_, numbers) = parse::split_once(s, s, ":", "expected ':' after the card id")?;
+    let (have, winning) = parse::split_once(s, numbers, "|", "expected '|' between the numbers")?;
+
+    Ok(ScratchCard {
+        numbers_you_have: parse::numbers(s, have)?,
+        winning_numbers: parse::numbers(s, winning)?,
+    })
 }
 
 #[cfg(test)]
@@ -63,13 +103,20 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/4.txt");
-        assert_eq!(part_1(&input), 21485);
+        let input = util::read_input("./input/4.txt");
+        assert_eq!(part_1(&input), Ok(21485));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/4.txt");
-        assert_eq!(part_2(&input), 11024379);
+        let input = util::read_input("./input/4.txt");
+        assert_eq!(part_2(&input), Ok(11024379));
+    }
+
+    #[test]
+    pub fn test_to_card_errors() {
+        let err = to_card("Card 1: 41 4x | 83").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (12, "4x"));
+        assert!(to_card("Card 1: 41 48 83").is_err());
     }
 }
@@ -1,8 +1,124 @@
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::repl::{self, Inspect};
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::almanac::{Almanac, MapRange, Mapping, RangedAlmanac, U64range};
+use itertools::Itertools;
 use std::collections::HashSet;
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    let almanac = Almanac::from(input);
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn inspect(&self, input: &Input) -> Result<Option<Box<dyn Inspect>>, ParseError> {
+        Ok(Some(Box::new(Almanac::from(input)?)))
+    }
+}
+
+impl Inspect for Almanac {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("seeds", "list the seeds"),
+            ("maps", "list the maps in the order they're applied"),
+            ("map <n>", "show the ranges of the nth map"),
+            ("trace <seed>", "follow a seed through every map"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        let output = match command {
+            "seeds" => Ok(self.seeds.iter().map(|s| s.to_string()).join(" ")),
+            "maps" => Ok(self
+                .maps
+                .iter()
+                .enumerate()
+                .map(|(i, m)| format!("{:>2}  {} ({} ranges)", i, m.title, m.ranges.len()))
+                .join("\n")),
+            "map" => repl::arg::<usize>(args, 0, "map").and_then(|n| {
+                let map = self
+                    .maps
+                    .get(n)
+                    .ok_or_else(|| format!("there are {} maps", self.maps.len()))?;
+                let ranges = map
+                    .ranges
+                    .iter()
+                    .map(|r| format!("{} {} {}", r.dest_start, r.src_start, r.range));
+                Ok(std::iter::once(map.title.clone()).chain(ranges).join("\n"))
+            }),
+            "trace" => repl::arg::<u64>(args, 0, "seed").map(|seed| {
+                let mut value = seed;
+                let mut steps = vec![format!(
+                    "seed {}",
+                    seed
+                )];
+                for map in &self.maps {
+                    value = next(map, value);
+                    steps.push(format!("{} -> {}", map.title, value));
+                }
+                steps.join("\n")
+            }),
+            _ => return None,
+        };
+        Some(output)
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4",
+    part_1: Some("35"),
+    part_2: Some("46"),
+}];
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let almanac = Almanac::from(input)?;
     let mut lowest: u64 = u64::MAX;
     for seed in almanac.seeds {
         let mut value = seed;
@@ -14,7 +130,7 @@ pub fn part_1(input: &Vec<String>) -> u64 {
         }
     }
 
-    lowest
+    Ok(lowest)
 }
 
 // WIP. Originally brute forced, trying to get range math working correctly.
@@ -29,8 +145,8 @@ pub fn part_1(input: &Vec<String>) -> u64 {
  *      convert intersection range to map range's destination range
  *      put "remainders" , if any, back on the stack
  */
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let ranged_almanac: RangedAlmanac = RangedAlmanac::from(input);
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let ranged_almanac: RangedAlmanac = RangedAlmanac::from(input)?;
     let mut ranges: HashSet<U64range> = HashSet::new();
     // set the initial ranges as the seed ranges from the almanac
     for seed in ranged_almanac.seeds {
@@ -48,7 +164,7 @@ pub fn part_2(input: &Vec<String>) -> u64 {
             }
         }
     }
-    return ranges.into_iter().min().unwrap().0;
+    return Ok(ranges.into_iter().min().unwrap().0);
 }
 
 fn is_in_range(r: &MapRange, n: u64) -> bool {
@@ -78,15 +194,37 @@ mod tests {
     use super::*;
     // FIXME Move to integration tests
 
+    #[test]
+    pub fn test_inspect() {
+        let almanac = Day5.inspect(&EXAMPLES[0].input()).unwrap().unwrap();
+        let run = |command: &str, args: &[&str]| almanac.run(command, args).unwrap();
+        assert_eq!(run("seeds", &[]), Ok(String::from("79 14 55 13")));
+        assert_eq!(
+            run("map", &["6"]),
+            Ok(String::from("humidity-to-location\n60 56 37\n56 93 4"))
+        );
+        assert!(run("map", &["7"]).is_err());
+        assert!(run("trace", &["79"]).unwrap().ends_with("humidity-to-location -> 82"));
+        assert!(almanac.run("path", &[]).is_none());
+    }
+
+    #[test]
+    pub fn test_part2_seeds_around_a_map_range() {
+        // seeds 40 to 109 run past both ends of some map ranges. Checking each
+        // seed with part 1 gives location 0, for seed 70
+        let input = EXAMPLES[0].input.replacen("79 14 55 13", "40 70", 1);
+        assert_eq!(part_2(&Input::from(input)), Ok(0));
+    }
+
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/5.txt");
-        assert_eq!(part_1(&input), 1181555926);
+        let input = util::read_input("./input/5.txt");
+        assert_eq!(part_1(&input), Ok(1181555926));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/5.txt");
-        assert_eq!(part_2(&input), 37806486);
+        let input = util::read_input("./input/5.txt");
+        assert_eq!(part_2(&input), Ok(37806486));
     }
 }
@@ -1,9 +1,64 @@
-use crate::util;
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+use crate::util::parse;
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    // parse inputs
-    let times = util::parse_number_vec_following_colon(&input[0]);
-    let distances = util::parse_number_vec_following_colon(&input[1]);
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+Time:      7  15   30
+Distance:  9  40  200",
+        part_1: Some("288"),
+        part_2: None,
+    },
+    Example {
+        input: "\
+Time:      71530
+Distance:  940200",
+        part_1: None,
+        part_2: Some("71503"),
+    },
+];
+
+// the race times and record distances from the first two lines
+fn parse_races(input: &Input) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    let line = |i: usize| {
+        let s = input
+            .get(i)
+            .ok_or_else(|| ParseError::at_end("", "missing line").on_line(i + 1))?;
+        parse::labelled_numbers(s).map(|(_, v)| v).map_err(|e| e.on_line(i + 1))
+    };
+    let (times, distances) = (line(0)?, line(1)?);
+    if times.len() != distances.len() {
+        return Err(
+            ParseError::at(&input[1], &input[1], "expected a distance for every time").on_line(2),
+        );
+    }
+    Ok((times, distances))
+}
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let (times, distances) = parse_races(input)?;
     let mut ways_to_beat_record: Vec<u64> = Vec::new();
     for (i, time) in times.into_iter().enumerate() {
         let target_distance = distances[i];
@@ -15,13 +70,11 @@ pub fn part_1(input: &Vec<String>) -> u64 {
         }
         ways_to_beat_record.push(winning_options);
     }
-    return ways_to_beat_record.iter().fold(1, |acc, &e| acc * e);
+    return Ok(ways_to_beat_record.iter().fold(1, |acc, &e| acc * e));
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    // parse inputs
-    let times = util::parse_number_vec_following_colon(&input[0]);
-    let distances = util::parse_number_vec_following_colon(&input[1]);
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let (times, distances) = parse_races(input)?;
     let mut ways_to_beat_record: Vec<u64> = Vec::new();
     for (i, time) in times.into_iter().enumerate() {
         let target_distance = distances[i];
@@ -33,7 +86,7 @@ pub fn part_2(input: &Vec<String>) -> u64 {
         }
         ways_to_beat_record.push(winning_options);
     }
-    return *ways_to_beat_record.get(0).unwrap();
+    return Ok(*ways_to_beat_record.get(0).unwrap());
 }
 
 #[cfg(test)]
@@ -45,13 +98,13 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/6.txt");
-        assert_eq!(part_1(&input), 1624896);
+        let input = util::read_input("./input/6.txt");
+        assert_eq!(part_1(&input), Ok(1624896));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/6.txt");
-        assert_eq!(part_2(&input), 32583852);
+        let input = util::read_input("./input/6.txt");
+        assert_eq!(part_2(&input), Ok(32583852));
     }
 }
@@ -1,6 +1,40 @@
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::camel_card::{compare_bid, parse_bid, parse_hand, Bid, CamelCard, HandType};
 use std::collections::HashSet;
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483",
+    part_1: Some("6440"),
+    part_2: Some("5905"),
+}];
+
 fn rank(h: &Vec<CamelCard>) -> HandType {
     let unique_cards: HashSet<&CamelCard> = HashSet::from_iter(h.iter());
     match unique_cards.len() {
@@ -74,24 +108,35 @@ fn rank_jokers(h: &Vec<CamelCard>) -> HandType {
     };
 }
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    let mut bids: Vec<Bid> = input.iter().map(|s| parse_bid(s, parse_hand, rank)).collect();
+fn parse_bids(
+    input: &Input,
+    hand_rank_strategy: fn(&Vec<CamelCard>) -> HandType,
+) -> Result<Vec<Bid>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, s)| parse_bid(s, parse_hand, hand_rank_strategy).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let mut bids: Vec<Bid> = parse_bids(input, rank)?;
     bids.sort_by(compare_bid);
     let mut result: u64 = 0;
     for (i, bid) in bids.into_iter().enumerate() {
         result += ((i + 1) * bid.amount as usize) as u64;
     }
-    result
+    Ok(result)
 }
 
-pub fn part_2(input: &Vec<String>) -> u64 {
-    let mut bids: Vec<Bid> = input.iter().map(|s| parse_bid(s, parse_hand, rank_jokers)).collect();
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
+    let mut bids: Vec<Bid> = parse_bids(input, rank_jokers)?;
     bids.sort_by(compare_bid);
     let mut result: u64 = 0;
     for (i, bid) in bids.into_iter().enumerate() {
         result += (((i as u64) + 1) * bid.amount as u64) as u64;
     }
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -103,13 +148,22 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/7.txt");
-        assert_eq!(part_1(&input), 247815719);
+        let input = util::read_input("./input/7.txt");
+        assert_eq!(part_1(&input), Ok(247815719));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/7.txt");
-        assert_eq!(part_2(&input), 248747492);
+        let input = util::read_input("./input/7.txt");
+        assert_eq!(part_2(&input), Ok(248747492));
+    }
+
+    #[test]
+    pub fn test_parse_bid_errors() {
+        let err = parse_bid("32T3X 765", parse_hand, rank).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (1, "32T3X"));
+        let err = parse_bid("32T3K 7x5", parse_hand, rank).unwrap_err();
+        assert_eq!(err.column, 7);
+        assert!(parse_bid("32T3K", parse_hand, rank).is_err());
     }
 }
@@ -1,127 +1,202 @@
-use regex::Regex;
+use crate::cancel;
+use crate::error::ParseError;
+use crate::info;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+use crate::util::{math, parse};
 use std::{collections::HashMap, time::Instant};
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_1(input).map(Answer::from)
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        part_2(input).map(Answer::from)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)",
+        part_1: Some("2"),
+        part_2: None,
+    },
+    Example {
+        input: "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+        part_1: Some("6"),
+        part_2: None,
+    },
+    Example {
+        input: "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        part_1: None,
+        part_2: Some("6"),
+    },
+];
+
 #[derive(Debug)]
-struct Map {
+struct Map<'a> {
     instructions: Vec<char>,
-    points: HashMap<String, (String, String)>,
+    points: HashMap<&'a str, (&'a str, &'a str)>,
 }
 
-fn parse_map(input: &Vec<String>) -> Map {
-    let mut iter = input.iter();
-    let instructions: Vec<char> = iter.next().unwrap().chars().collect();
-    iter.next().unwrap(); // eat the separator line
-    let mut nodes: HashMap<String, (String, String)> = HashMap::new();
-    // need to break this out to separate strings,but matches AAA = (BBB, CCC) with match groups AAA, BBB, CCC
-    let node_pattern =
-        Regex::new(r"^([1-9A-Z]{3})\W\=\W\(([1-9A-Z]{3})\,\W([1-9A-Z]{3})\)$").unwrap();
-    loop {
-        match iter.next() {
-            Some(line) => {
-                let c = node_pattern.captures(line).unwrap();
-                nodes.insert(
-                    String::from(c.get(1).unwrap().as_str()),
-                    (
-                        String::from(c.get(2).unwrap().as_str()),
-                        String::from(c.get(3).unwrap().as_str()),
-                    ),
-                );
-            }
-            None => break,
+fn parse_map(input: &Input) -> Result<Map<'_>, ParseError> {
+    let mut iter = input.lines().enumerate();
+    let instructions: Vec<char> = match iter.next() {
+        Some((_, line)) if !line.is_empty() => line.chars().collect(),
+        _ => return Err(ParseError::at_end("", "expected a line of L/R instructions").on_line(1)),
+    };
+    if let Some(i) = instructions.iter().position(|c| *c != 'L' && *c != 'R') {
+        let line = &input[0];
+        let (offset, c) = line.char_indices().nth(i).unwrap();
+        return Err(ParseError::at(
+            line,
+            &line[offset..offset + c.len_utf8()],
+            "expected L or R",
+        )
+        .on_line(1));
+    }
+    iter.next(); // eat the separator line
+    let mut nodes: HashMap<&str, (&str, &str)> = HashMap::new();
+    let mut lines: Vec<(usize, &str, (&str, &str))> = Vec::new();
+    // nodes like AAA = (BBB, CCC)
+    for (i, line) in iter {
+        let (node, next) = parse::key_pair(line).map_err(|e| e.on_line(i + 1))?;
+        nodes.insert(node, next);
+        lines.push((i, line, next));
+    }
+    // every node has to lead somewhere on the map, or walking it would get lost
+    for (i, line, (left, right)) in lines {
+        let next = match nodes.contains_key(left) {
+            true => right,
+            false => left,
+        };
+        if !nodes.contains_key(next) {
+            return Err(ParseError::at(line, next, "no node with this name").on_line(i + 1));
         }
     }
-    Map {
+    Ok(Map {
         instructions: instructions,
         points: nodes,
-    }
+    })
 }
 
-fn ending_in_z_strategy(pos: &String) -> bool {
+fn ending_in_z_strategy(pos: &str) -> bool {
     pos.ends_with("Z")
 }
 
-fn ending_all_z_strategy(pos: &String) -> bool {
+fn ending_all_z_strategy(pos: &str) -> bool {
     pos.chars().all(|c| c == 'Z')
 }
 
-fn count_steps_to_end(map: &Map, start: &String, end_strategy: fn(&String) -> bool) -> u64 {
+// the start is only checked here, so None means there's no node to start from
+fn count_steps_to_end(map: &Map, start: &str, end_strategy: fn(&str) -> bool) -> Option<u64> {
     let mut total_steps: u64 = 0;
     let mut current_pos = start;
     let mut instructions = map.instructions.clone();
     while !end_strategy(current_pos) {
-        let directions = map.points.get(current_pos).unwrap();
+        cancel::checkpoint();
+        let directions = map.points.get(current_pos)?;
         // this could be more efficient with iterator , but for now it's fine to jus treat the instructions
         // as a queue
         let next_direction = instructions.remove(0);
         current_pos = match next_direction {
-            'L' => &directions.0,
-            'R' => &directions.1,
+            'L' => directions.0,
+            'R' => directions.1,
             _ => panic!("invalid direction"),
         };
         // put on end of queue
         instructions.push(next_direction);
         total_steps += 1
     }
-    total_steps
+    Some(total_steps)
 }
 
-pub fn part_1(input: &Vec<String>) -> u64 {
-    let desert_map: Map = parse_map(input);
-    return count_steps_to_end(&desert_map, &String::from("AAA"), ending_all_z_strategy);
+// a start node that isn't on the map is reported at the end of the input, as
+// that's where we gave up looking for it
+fn missing_node(input: &Input, node: &str) -> ParseError {
+    let last = input.len().max(1);
+    let line = input.get(last - 1).unwrap_or("");
+    ParseError::at_end(line, format!("there's no node named {}", node)).on_line(last)
 }
 
-fn get_factors(n: u64) -> Vec<u64> {
-    // iterates over numbers 1-n, filters by n being divisible by x
-    (1..n / 2 + 1).into_iter().filter(|&x| n % x == 0).collect::<Vec<u64>>()
+pub fn part_1(input: &Input) -> Result<u64, ParseError> {
+    let desert_map: Map = parse_map(input)?;
+    count_steps_to_end(&desert_map, "AAA", ending_all_z_strategy)
+        .ok_or_else(|| missing_node(input, "AAA"))
 }
 
-/**the LCM of two numbers 'a' and 'b' is equal to the product of the 2 numbers divided by the highest common factor (HCF) of the 2 numbers. */
-fn get_lcm(a: u64, b: u64) -> u64 {
-    let smaller = a.min(b);
-    let bigger = a.max(b);
-    if bigger % smaller == 0 {
-        return a.min(b);
-    }
-    let smaller_factors = get_factors(smaller);
-    let hcf = smaller_factors.iter().filter(|a| b % *a == 0).max().unwrap();
-    (a * b) / hcf
-}
-
-fn get_lcm_of_vec(n: &Vec<u64>) -> u64 {
-    let mut stack = n.clone();
-    while stack.len() > 1 {
-        let a = stack.pop().unwrap();
-        let b = stack.pop().unwrap();
-        stack.push(get_lcm(a, b))
-    }
-    return stack.get(0).unwrap().clone();
-}
-
-pub fn part_2(input: &Vec<String>) -> u64 {
+pub fn part_2(input: &Input) -> Result<u64, ParseError> {
     // parse the map
     // collect a list of all nodes that end with A
     // on each step, iterate all of the nodes toward the next step
     // break loop once every node is ending with Z
-    let m: Map = parse_map(input);
+    let m: Map = parse_map(input)?;
     let start: Instant = Instant::now();
 
     let step_counts: Vec<u64> = m
         .points
         .keys()
         .filter(|k| k.ends_with("A"))
-        .map(|p| count_steps_to_end(&m, p, ending_in_z_strategy))
-        .collect();
+        .map(|p| {
+            count_steps_to_end(&m, p, ending_in_z_strategy).ok_or_else(|| missing_node(input, p))
+        })
+        .collect::<Result<_, _>>()?;
 
-    let total_steps = get_lcm_of_vec(&step_counts);
-    println!(
+    let total_steps = steps_until_all_meet(&step_counts)?;
+    info!(
         "Finished finding LCM of steps after {:?} ms",
         start.elapsed().as_millis()
     );
-    total_steps
+    Ok(total_steps)
+}
+
+// each ghost loops back round to its Z node, so they all meet at the lcm. The
+// counts come from the map, so one too big to count is reported against it
+fn steps_until_all_meet(step_counts: &[u64]) -> Result<u64, ParseError> {
+    math::checked_lcm_of(step_counts.iter().copied())
+        .ok_or_else(|| ParseError::at_end("", "the ghosts' step count overflows a u64").on_line(1))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::panic;
+
+    use crate::cancel::{CancelToken, Cancelled};
     use crate::util;
 
     use super::*;
@@ -129,13 +204,47 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/8.txt");
-        assert_eq!(part_1(&input), 20777);
+        let input = util::read_input("./input/8.txt");
+        assert_eq!(part_1(&input), Ok(20777));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/8.txt");
-        assert_eq!(part_2(&input), 13289612809129);
+        let input = util::read_input("./input/8.txt");
+        assert_eq!(part_2(&input), Ok(13289612809129));
+    }
+
+    #[test]
+    pub fn test_steps_until_all_meet() {
+        assert_eq!(steps_until_all_meet(&[2, 3, 4]), Ok(12));
+        let err = steps_until_all_meet(&[
+            3,
+            u64::MAX - 1,
+            u64::MAX,
+        ])
+        .unwrap_err();
+        assert_eq!(err.message, "the ghosts' step count overflows a u64");
+    }
+
+    #[test]
+    pub fn test_walk_can_be_cancelled() {
+        // a walk on a huge map can take a while, so it has to stop once cancelled
+        let input = Input::from("LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)");
+        let token = CancelToken::new();
+        token.cancel();
+        let result = panic::catch_unwind(|| cancel::with_token(token, || part_1(&input)));
+        assert!(result.unwrap_err().downcast_ref::<Cancelled>().is_some());
+    }
+
+    #[test]
+    pub fn test_parse_map_errors() {
+        let err = parse_map(&Input::from("LRX\n\nAAA = (BBB, CCC)")).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+        let err = parse_map(&Input::from("LR\n\nAAA = (BBB, CCC)\nBBB = BBB")).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 7));
+        let err = parse_map(&Input::from("LR\n\nAAA = (AAA, CCC)")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 13, "CCC"));
+        let err = part_1(&Input::from("LR\n\nBBB = (BBB, BBB)")).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 17));
     }
 }
@@ -1,12 +1,44 @@
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
 use crate::structs::oasis_report::OasisReport;
 
-pub fn part_1(input: &Vec<String>) -> i64 {
-    let report = OasisReport::from(input);
-    report.points.iter().map(|p| p.extrapolate()).sum()
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input)?.into())
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45",
+    part_1: Some("114"),
+    part_2: Some("2"),
+}];
+
+pub fn part_1(input: &Input) -> Result<i64, ParseError> {
+    let report = OasisReport::from(input)?;
+    Ok(report.points.iter().map(|p| p.extrapolate()).sum())
 }
 
-pub fn part_2(input: &Vec<String>) -> i64 {
-    OasisReport::from(input).points.iter().map(|p| p.extrapolate_backward()).sum()
+pub fn part_2(input: &Input) -> Result<i64, ParseError> {
+    Ok(OasisReport::from(input)?.points.iter().map(|p| p.extrapolate_backward()).sum())
 }
 
 #[cfg(test)]
@@ -18,13 +50,13 @@ mod tests {
 
     #[test]
     pub fn test_part1() {
-        let input = util::read_lines("./input/9.txt");
-        assert_eq!(part_1(&input), 2005352194);
+        let input = util::read_input("./input/9.txt");
+        assert_eq!(part_1(&input), Ok(2005352194));
     }
 
     #[test]
     pub fn test_part2() {
-        let input = util::read_lines("./input/9.txt");
-        assert_eq!(part_2(&input), 1077);
+        let input = util::read_input("./input/9.txt");
+        assert_eq!(part_2(&input), Ok(1077));
     }
 }
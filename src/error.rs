@@ -0,0 +1,152 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::input::Input;
+
+/**
+ * An error in a puzzle input, pointing at the offending text. Parsers that only
+ * see a single line leave the line number at 0 for the caller to fill in with
+ * `on_line`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /**
+     * An error about `token`, which should be a slice of `line` so the column
+     * can be worked out. Anything else is reported against the start of the line.
+     */
+    pub fn at(line: &str, token: &str, message: impl Into<String>) -> ParseError {
+        let start = line.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        let column = match offset <= line.len() {
+            true => line[..offset].chars().count() + 1,
+            false => 1,
+        };
+        ParseError {
+            line: 0,
+            column,
+            text: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /**
+     * An error about something missing from the end of `line`
+     */
+    pub fn at_end(line: &str, message: impl Into<String>) -> ParseError {
+        ParseError::at(line, &line[line.len()..], message)
+    }
+
+    /**
+     * Sets the (1 based) line number the error happened on
+     */
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    /**
+     * Formats the error the way rustc does, quoting the offending line from
+     * `input` and underlining the bad text
+     *
+     *  error: expected a number
+     *   --> input/2.txt:3:9
+     *    |
+     *  3 | Game 3: x blue
+     *    |         ^
+     */
+    pub fn diagnostic(&self, source: &str, input: &Input) -> String {
+        let line_no = self.line.to_string();
+        let gutter = " ".repeat(line_no.len());
+        let source_line = input.get(self.line.wrapping_sub(1)).unwrap_or("");
+        let underline = "^".repeat(self.text.chars().count().max(1));
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.message,
+            gutter,
+            source,
+            self.line,
+            self.column,
+            gutter,
+            line_no,
+            source_line,
+            gutter,
+            " ".repeat(self.column.saturating_sub(1)),
+            underline
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " ({:?})", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/**
+ * Parses `token`, a slice of `line`, as a number
+ */
+pub fn parse_number<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::at(line, token, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_column() {
+        let line = "Game 3: x blue";
+        let err = ParseError::at(line, &line[8..9], "expected a number").on_line(3);
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 9);
+        assert_eq!(err.text, "x");
+        assert_eq!(ParseError::at_end(line, "missing").column, 15);
+        // text that isn't part of the line is reported at the start
+        assert_eq!(ParseError::at(line, "x", "bad").column, 1);
+    }
+
+    #[test]
+    pub fn test_parse_number() {
+        let line = "1 22 x3";
+        assert_eq!(parse_number::<u32>(line, &line[2..4]), Ok(22));
+        let err = parse_number::<u32>(line, &line[5..]).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "x3"));
+    }
+
+    #[test]
+    pub fn test_diagnostic() {
+        let input = Input::from("Game 1: 3 blue\nGame 2: xx blue");
+        let err = ParseError::at(&input[1], &input[1][8..10], "expected a number").on_line(2);
+        let expected = "\
+error: expected a number
+ --> input/2.txt:2:9
+  |
+2 | Game 2: xx blue
+  |         ^^
+";
+        assert_eq!(err.diagnostic("input/2.txt", &input), expected);
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 9: expected a number (\"xx\")"
+        );
+        // a hand built error with no column is underlined from the start of the line
+        let err = ParseError { column: 0, ..err };
+        assert!(err.diagnostic("input/2.txt", &input).ends_with("  | ^^\n"));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::input;
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2023;
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), " input fetcher");
+
+/**
+ * The least time between two requests to the puzzle server, across runs
+ */
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/**
+ * The file holding the session token when AOC_SESSION isn't set. It's the value
+ * of the `session` cookie from a logged in browser.
+ */
+pub fn session_path() -> PathBuf {
+    input::input_dir().join(".session")
+}
+
+/**
+ * Whether a day's input came from the cache or the server
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/**
+ * Talks to the puzzle server: downloads inputs into a directory, which doubles
+ * as the cache so a day with a file there is never requested again, and posts
+ * answers
+ */
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    dir: PathBuf,
+    min_interval: Duration,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str, dir: &Path, min_interval: Duration) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            dir: dir.to_path_buf(),
+            min_interval,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    /**
+     * A fetcher for the input directory, using AOC_BASE_URL if it's set and the
+     * session token from AOC_SESSION or the session file
+     */
+    pub fn from_env() -> Result<Fetcher, String> {
+        let base_url = match env::var(BASE_URL_VAR) {
+            Ok(url) if !url.is_empty() => url,
+            _ => String::from(DEFAULT_BASE_URL),
+        };
+        Ok(Fetcher::new(
+            &base_url,
+            &session()?,
+            &input::input_dir(),
+            MIN_INTERVAL,
+        ))
+    }
+
+    /**
+     * Makes sure a day's input is on disk, downloading it if it isn't yet
+     */
+    pub fn fetch(&self, day: u8) -> Result<Fetched, String> {
+        let path = self.dir.join(format!("{}.txt", day));
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        let body = self.download(day)?;
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("could not create {}: {}", self.dir.display(), e))?;
+        // written under another name first so an interrupted run leaves nothing
+        // behind that looks cached
+        let partial = path.with_extension("txt.part");
+        fs::write(&partial, body)
+            .and_then(|_| fs::rename(&partial, &path))
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /**
+     * Posts an answer for a part, returning the page the server responds with
+     */
+    pub fn post_answer(&self, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        self.wait_for_turn();
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", answer),
+            ]);
+        read_response(day, &url, response)
+    }
+
+    fn download(&self, day: u8) -> Result<String, String> {
+        self.wait_for_turn();
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let response =
+            self.agent.get(&url).set("Cookie", &format!("session={}", self.session)).call();
+        read_response(day, &url, response)
+    }
+
+    // sleeps until the minimum interval since the last request, which is kept
+    // in a file so it holds across runs
+    fn wait_for_turn(&self) {
+        let stamp = self.dir.join(".last-fetch");
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(|ms| UNIX_EPOCH + Duration::from_millis(ms));
+        if let Some(wait) =
+            last.and_then(|l| (l + self.min_interval).duration_since(SystemTime::now()).ok())
+        {
+            thread::sleep(wait);
+        }
+        // best effort, failing to write it only means the next run won't wait
+        let ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        let _ = fs::create_dir_all(&self.dir).and_then(|_| fs::write(&stamp, ms.to_string()));
+    }
+}
+
+fn read_response(
+    day: u8,
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| format!("could not read the response from {}: {}", url, e)),
+        Err(ureq::Error::Status(404, _)) => {
+            Err(format!("day {} isn't available yet ({})", day, url))
+        }
+        Err(ureq::Error::Status(400 | 401 | 500, _)) => Err(format!(
+            "{} was rejected, the session token may have expired",
+            url
+        )),
+        Err(ureq::Error::Status(code, _)) => Err(format!("{} returned {}", url, code)),
+        Err(e) => Err(format!("could not reach {}: {}", url, e)),
+    }
+}
+
+fn session() -> Result<String, String> {
+    if let Ok(token) = env::var(SESSION_VAR) {
+        if !token.trim().is_empty() {
+            return Ok(token);
+        }
+    }
+    let path = session_path();
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token),
+        _ => Err(format!(
+            "no session token found. Set {} or save it in {}",
+            SESSION_VAR,
+            path.display()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        time::Instant,
+    };
+
+    use super::*;
+
+    // serves `responses` in order, one per connection, returning the requests it
+    // received
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request += &line;
+                }
+                let length = request
+                    .lines()
+                    .find_map(|l| l.strip_prefix("Content-Length: "))
+                    .map_or(0, |n| n.trim().parse().unwrap());
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+                requests.push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    pub fn test_fetch_caches() {
+        let (url, server) = serve(vec![(200, "1 2 3\n")]);
+        let dir = temp_dir("cache");
+        let fetcher = Fetcher::new(&url, "abc\n", &dir, Duration::ZERO);
+
+        let path = dir.join("9.txt");
+        assert_eq!(fetcher.fetch(9), Ok(Fetched::Downloaded(path.clone())));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+        // the server only answers once, so this has to come from disk
+        assert_eq!(fetcher.fetch(9), Ok(Fetched::Cached(path)));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_post_answer() {
+        let (url, server) = serve(vec![(200, "<main>That's the right answer!</main>")]);
+        let dir = temp_dir("post");
+        let fetcher = Fetcher::new(&url, "abc", &dir, Duration::ZERO);
+
+        let page = fetcher.post_answer(4, 2, "1234").unwrap();
+        assert_eq!(page, "<main>That's the right answer!</main>");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/4/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\nlevel=2&answer=1234"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_fetch_errors_are_not_cached() {
+        let (url, server) = serve(vec![
+            (404, "Not found"),
+            (400, "Please log in"),
+        ]);
+        let dir = temp_dir("errors");
+        let fetcher = Fetcher::new(&url, "abc", &dir, Duration::ZERO);
+
+        assert!(fetcher.fetch(25).unwrap_err().contains("isn't available yet"));
+        assert!(fetcher.fetch(25).unwrap_err().contains("session token"));
+        assert!(!dir.join("25.txt").exists());
+        server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_fetch_waits_between_requests() {
+        let (url, server) = serve(vec![
+            (200, "a"),
+            (200, "b"),
+        ]);
+        let dir = temp_dir("interval");
+        let interval = Duration::from_millis(300);
+        let fetcher = Fetcher::new(&url, "abc", &dir, interval);
+
+        let start = Instant::now();
+        fetcher.fetch(1).unwrap();
+        fetcher.fetch(2).unwrap();
+        assert!(start.elapsed() >= interval);
+        server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    env,
+    ffi::OsString,
+    fmt,
+    io::{self, Read},
+    ops::{Index, Range},
+    path::PathBuf,
+    sync::OnceLock,
+};
+
+use crate::{solution, util};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "./input";
+
+/**
+ * Where a solver's input is read from
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    // <input dir>/<day>.txt
+    Default,
+    File(PathBuf),
+    Stdin,
+    // the nth (from 1) registered example that has an answer for the part being run
+    Example(usize),
+}
+
+impl InputSource {
+    /**
+     * Parses the value given to `--input`, where `-` means stdin
+     */
+    pub fn from_arg(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /**
+     * Where the input for a day comes from, for messages
+     */
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            InputSource::Default => day_input_path(day).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => String::from("<stdin>"),
+            InputSource::Example(n) => format!("<example {}>", n),
+        }
+    }
+
+    /**
+     * The file the input for a day is read from, if it comes from one
+     */
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Default => Some(day_input_path(day)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin | InputSource::Example(_) => None,
+        }
+    }
+
+    pub fn read(&self, day: u8, part: u8) -> Result<Input, String> {
+        match self {
+            InputSource::Default => read_file(day_input_path(day), true),
+            InputSource::File(path) => read_file(path.clone(), false),
+            InputSource::Stdin => read_stdin(),
+            InputSource::Example(n) => read_example(day, part, *n),
+        }
+    }
+}
+
+/**
+ * A puzzle input. The text is held once and lines, blocks and grids are handed
+ * out as slices of it, so parsing doesn't need to copy it.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Input {
+    text: String,
+    // the byte range of each line, without its line ending
+    lines: Vec<Range<usize>>,
+}
+
+impl Input {
+    /**
+     * Splits text into lines the way `str::lines` does
+     */
+    pub fn new(text: String) -> Input {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in text.split_inclusive('\n') {
+            let content = line.strip_suffix('\n').unwrap_or(line);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+            lines.push(start..start + content.len());
+            start += line.len();
+        }
+        Input { text, lines }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn get(&self, line: usize) -> Option<&str> {
+        self.lines.get(line).map(|r| &self.text[r.clone()])
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator + Clone {
+        self.lines.iter().map(|r| &self.text[r.clone()])
+    }
+
+    /**
+     * The runs of lines separated by blank ones. Whitespace only lines count as
+     * blank, and any number of them in a row make a single separator.
+     */
+    pub fn blocks(&self) -> Blocks<'_> {
+        Blocks {
+            input: self,
+            next: 0,
+        }
+    }
+
+    fn is_blank(&self, line: usize) -> bool {
+        self[line].trim().is_empty()
+    }
+
+    /**
+     * The input as a grid of characters, as wide as its first line
+     */
+    pub fn grid(&self) -> Grid<'_> {
+        Grid {
+            input: self,
+            width: self.get(0).map_or(0, |l| l.len()),
+        }
+    }
+}
+
+impl Index<usize> for Input {
+    type Output = str;
+
+    fn index(&self, line: usize) -> &str {
+        &self.text[self.lines[line].clone()]
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Input::new(text)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input::new(String::from(text))
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/**
+ * Iterates over the blocks of an input, finding each one as it's asked for
+ */
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    input: &'a Input,
+    // the line to start looking for the next block from
+    next: usize,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Block<'a>> {
+        let input = self.input;
+        let start = (self.next..input.len()).find(|l| !input.is_blank(*l))?;
+        let end = (start..input.len()).find(|l| input.is_blank(*l)).unwrap_or(input.len());
+        self.next = end;
+        Some(Block { input, start, end })
+    }
+}
+
+/**
+ * A run of non-blank lines in an input
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Block<'a> {
+    input: &'a Input,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Block<'a> {
+    /**
+     * The (1 based) line of the input the block starts on, for error messages
+     */
+    pub fn line_number(&self) -> usize {
+        self.start + 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn get(&self, line: usize) -> Option<&'a str> {
+        match line < self.len() {
+            true => self.input.get(self.start + line),
+            false => None,
+        }
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &'a str> + ExactSizeIterator + Clone {
+        let input = self.input;
+        (self.start..self.end).map(move |l| &input[l])
+    }
+}
+
+impl Index<usize> for Block<'_> {
+    type Output = str;
+
+    fn index(&self, line: usize) -> &str {
+        self.get(line).expect("line outside the block")
+    }
+}
+
+/**
+ * A view of an input as rows of single byte cells. Cells past the end of a
+ * short row don't exist.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Grid<'a> {
+    input: &'a Input,
+    width: usize,
+}
+
+impl<'a> Grid<'a> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.input.len()
+    }
+
+    pub fn row(&self, row: usize) -> Option<&'a str> {
+        self.input.get(row)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a str> + Clone {
+        self.input.lines()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<char> {
+        match self.row(row) {
+            Some(r) if col < self.width => r.as_bytes().get(col).map(|b| *b as char),
+            _ => None,
+        }
+    }
+
+    /**
+     * The first cell holding `c`, scanning row by row
+     */
+    pub fn find(&self, c: char) -> Option<(usize, usize)> {
+        self.rows()
+            .enumerate()
+            .find_map(|(row, r)| r.find(c).filter(|col| *col < self.width).map(|col| (row, col)))
+    }
+
+    /**
+     * Every cell with its position, row by row
+     */
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), char)> + 'a {
+        let width = self.width;
+        self.input.lines().enumerate().flat_map(move |(row, r)| {
+            r.bytes().take(width).enumerate().map(move |(col, b)| ((row, col), b as char))
+        })
+    }
+}
+
+/**
+ * The directory holding inputs, answers and benchmark baselines. Defaults to
+ * `./input`, and can be overridden with the AOC_INPUT_DIR environment variable.
+ */
+pub fn input_dir() -> PathBuf {
+    input_dir_from(env::var_os(INPUT_DIR_VAR))
+}
+
+fn input_dir_from(var: Option<OsString>) -> PathBuf {
+    match var {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(DEFAULT_INPUT_DIR),
+    }
+}
+
+pub fn day_input_path(day: u8) -> PathBuf {
+    input_dir().join(format!("{}.txt", day))
+}
+
+fn read_file(path: PathBuf, is_default: bool) -> Result<Input, String> {
+    util::try_read_input(&path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound if is_default => format!(
+            "input file {} not found. Save the puzzle input there, set {} or pass --input <file>",
+            path.display(),
+            INPUT_DIR_VAR
+        ),
+        io::ErrorKind::NotFound => format!("input file {} not found", path.display()),
+        _ => format!("could not read {}: {}", path.display(), e),
+    })
+}
+
+fn read_example(day: u8, part: u8, n: usize) -> Result<Input, String> {
+    let solution =
+        solution::find(day).ok_or_else(|| format!("no solution registered for day {}", day))?;
+    let examples = solution.examples_for(part);
+    match examples.get(n.saturating_sub(1)) {
+        Some(example) if n > 0 => Ok(example.input()),
+        _ if examples.is_empty() => Err(format!("day {} has no examples for part {}", day, part)),
+        _ => Err(format!(
+            "day {} has {} example(s) for part {}, not {}",
+            day,
+            examples.len(),
+            part,
+            n
+        )),
+    }
+}
+
+// stdin can only be consumed once, so it's read on first use and shared by
+// every task after that
+fn read_stdin() -> Result<Input, String> {
+    static STDIN: OnceLock<Result<Input, String>> = OnceLock::new();
+    STDIN
+        .get_or_init(|| {
+            let mut buf = String::new();
+            io::stdin()
+                .read_to_string(&mut buf)
+                .map_err(|e| format!("could not read stdin: {}", e))?;
+            Ok(Input::new(buf))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    pub fn test_input_dir_from() {
+        assert_eq!(input_dir_from(None), PathBuf::from("./input"));
+        assert_eq!(
+            input_dir_from(Some(OsString::new())),
+            PathBuf::from("./input")
+        );
+        assert_eq!(
+            input_dir_from(Some(OsString::from("/tmp/aoc"))),
+            PathBuf::from("/tmp/aoc")
+        );
+    }
+
+    #[test]
+    pub fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("a.txt"),
+            InputSource::File(PathBuf::from("a.txt"))
+        );
+    }
+
+    #[test]
+    pub fn test_read_file() {
+        let path = env::temp_dir().join(format!("aoc-input-test-{}.txt", std::process::id()));
+        fs::write(&path, "abc\n\ndef\n").unwrap();
+        let lines = InputSource::File(path.clone()).read(1, 1);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(lines, Ok(Input::from("abc\n\ndef\n")));
+    }
+
+    #[test]
+    pub fn test_input() {
+        let input = Input::from("ab\r\n\n  \ncd\nef\n\n");
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            vec!["ab", "", "  ", "cd", "ef", ""]
+        );
+        assert_eq!((input.len(), &input[3], input.get(6)), (6, "cd", None));
+        assert_eq!(
+            input.blocks().map(|b| b.lines().collect::<Vec<_>>()).collect::<Vec<_>>(),
+            vec![
+                vec!["ab"],
+                vec!["cd", "ef"]
+            ]
+        );
+        assert!(Input::from("").is_empty());
+        assert_eq!(Input::from("a\nb").len(), 2);
+    }
+
+    #[test]
+    pub fn test_blocks() {
+        let input = Input::from("\n \nab\ncd\n\n\t\n\nef");
+        let blocks: Vec<Block> = input.blocks().collect();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0].line_number(), blocks[0].len()), (3, 2));
+        assert_eq!((&blocks[0][1], blocks[0].get(2)), ("cd", None));
+        assert_eq!(blocks[1].line_number(), 8);
+        assert_eq!(blocks[1].lines().collect::<Vec<_>>(), vec!["ef"]);
+        assert_eq!(Input::from(" \n\n").blocks().count(), 0);
+    }
+
+    #[test]
+    pub fn test_grid() {
+        let input = Input::from("#.S\n..\n.#.");
+        let grid = input.grid();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(0, 2), Some('S'));
+        assert_eq!(grid.get(1, 2), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.find('S'), Some((0, 2)));
+        assert_eq!(grid.cells().filter(|(_, c)| *c == '#').count(), 2);
+        assert_eq!(grid.cells().nth(3), Some(((1, 0), '.')));
+    }
+
+    #[test]
+    pub fn test_read_example() {
+        let first = InputSource::Example(1).read(8, 1).unwrap();
+        assert_eq!(&first[0], "RL");
+        let second = InputSource::Example(2).read(8, 1).unwrap();
+        assert_eq!(&second[0], "LLR");
+        // examples only count towards the parts they have answers for
+        let part_2 = InputSource::Example(1).read(8, 2).unwrap();
+        assert_eq!(&part_2[2], "11A = (11B, XXX)");
+        assert!(InputSource::Example(2).read(8, 2).is_err());
+        assert!(InputSource::Example(1).read(16, 1).is_err());
+    }
+
+    #[test]
+    pub fn test_missing_file_error() {
+        let err = InputSource::File(PathBuf::from("/nonexistent/5.txt")).read(5, 1).unwrap_err();
+        assert_eq!(err, "input file /nonexistent/5.txt not found");
+    }
+}
@@ -0,0 +1,39 @@
+/**
+ * Advent of Code 2023 solutions. Each day lives in its own `dayN` module with
+ * public `part_1` and `part_2` functions and is registered in
+ * `solution::SOLUTIONS`. The shared puzzle structures are in `structs` and the
+ * helpers for running, verifying and benchmarking solvers are alongside them.
+ */
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod fetch;
+pub mod input;
+pub mod log;
+pub mod memstats;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod structs;
+pub mod submit;
+pub mod util;
+pub mod watch;
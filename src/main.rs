@@ -1,133 +1,484 @@
-use std::time::Instant;
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod structs;
-mod util;
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use adventofcode2023::answers::{self, Answers, Verdict};
+use adventofcode2023::bench::{self, Baseline};
+use adventofcode2023::fetch::{Fetched, Fetcher};
+use adventofcode2023::input::InputSource;
+use adventofcode2023::log::{self, Level};
+use adventofcode2023::memstats;
+use adventofcode2023::repl::{self, Session};
+use adventofcode2023::report::{self, Format};
+use adventofcode2023::runner::{self, Outcome, RunResult, Task};
+use adventofcode2023::scaffold;
+use adventofcode2023::solution::{self, Answer};
+use adventofcode2023::submit::{self, Check, History, Response};
+use adventofcode2023::watch::Watcher;
+use cli::{BenchOptions, Command};
+
+mod cli;
+
+// only counts once enabled with --mem, so normal runs pay almost nothing for it
+#[global_allocator]
+static ALLOCATOR: memstats::CountingAllocator = memstats::CountingAllocator;
+
 fn main() {
-    println!("Advent of Code 2023");
-    let day: u8 = std::env::args()
-        .nth(1)
-        .expect("Missing day argument")
-        .parse()
-        .expect("Day should be a number");
-    let part: u8 = std::env::args()
-        .nth(2)
-        .expect("Missing part argument")
-        .parse()
-        .expect("part should be a number");
-    solve_day(day, part);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli = match cli::parse(&args) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    log::set_level(cli.log_level);
+    if cli.mem {
+        memstats::enable();
+    }
+    // structured output is only the records, so scripts can parse stdout as is
+    if cli.format == Format::Text {
+        println!("Advent of Code 2023");
+    }
+    let source = &cli.input;
+    match cli.command {
+        Command::Solve { day, part } if cli.format == Format::Text => {
+            solve_day(day, part, source, cli.timeout)
+        }
+        Command::Solve { day, part } => {
+            let results = runner::run_all(&[Task { day, part }], source, 1, cli.timeout);
+            print!("{}", report::results(&results, cli.format));
+            if results[0].outcome.error().is_some() {
+                std::process::exit(1);
+            }
+        }
+        Command::RunAll { days, part } => {
+            let tasks = runner::tasks_for(&days, part);
+            let results = runner::run_all(&tasks, source, cli.jobs, cli.timeout);
+            print!("{}", report::results(&results, cli.format));
+        }
+        Command::Verify { days, part, record } => {
+            verify(&days, part, record, source, cli.jobs, cli.timeout)
+        }
+        Command::Bench {
+            days,
+            part,
+            options,
+        } => bench(&days, part, &options, source),
+        Command::New { day } => new_day(day),
+        Command::Watch { day, part } => watch(Task { day, part }, source, cli.timeout),
+        Command::Fetch { days } => fetch(&days),
+        Command::Submit { day, part } => submit(Task { day, part }, source, cli.timeout),
+        Command::Repl { day } => repl(day, source),
+    }
+}
+
+fn repl(day: u8, source: &InputSource) {
+    let mut session = match Session::load(day, source) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Failed to load day {}: {}", day, e);
+            process::exit(1);
+        }
+    };
+    println!("Loaded {}, type help for the commands", source.name(day));
+    if let Err(e) = repl::run(&mut session, io::stdin().lock(), io::stdout()) {
+        eprintln!("REPL failed: {}", e);
+        process::exit(1);
+    }
 }
 
-fn solve_day(day: u8, part: u8) {
+// the generated files go in this crate's own source tree
+fn new_day(day: u8) {
+    match scaffold::create(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to create day {}: {}", day, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn solve_day(day: u8, part: u8, source: &InputSource, timeout: Option<Duration>) {
     println!("Day {:?} Part {:?} : ", day, part);
-    let input = util::read_lines(format!("./input/{}.txt", day));
-    let start = Instant::now();
-    let result: u64 = match day {
-        1 => match part {
-            1 => day1::part_1(&input) as u64,
-            2 => day1::part_2(&input) as u64,
-            _ => panic!("Invalid part"),
-        },
-        2 => match part {
-            1 => day2::part_1(&input) as u64,
-            2 => day2::part_2(&input) as u64,
-            _ => panic!("Invalid part"),
-        },
-        3 => match part {
-            1 => day3::part_1(&input) as u64,
-            2 => day3::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        4 => match part {
-            1 => day4::part_1(&input),
-            2 => day4::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        5 => match part {
-            1 => day5::part_1(&input),
-            2 => day5::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        6 => match part {
-            1 => day6::part_1(&input),
-            2 => day6::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        7 => match part {
-            1 => day7::part_1(&input),
-            2 => day7::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        8 => match part {
-            1 => day8::part_1(&input),
-            2 => day8::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        10 => match part {
-            1 => day10::part_1(&input),
-            2 => day10::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        11 => match part {
-            1 => day11::part_1(&input),
-            2 => day11::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        12 => match part {
-            1 => day12::part_1(&input),
-            2 => day12::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        13 => match part {
-            1 => day13::part_1(&input),
-            2 => day13::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        14 => match part {
-            1 => day14::part_1(&input),
-            2 => day14::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        15 => match part {
-            1 => day15::part_1(&input),
-            2 => day15::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        16 => match part {
-            1 => day16::part_1(&input),
-            2 => day16::part_2(&input),
-            _ => panic!("Invalid part"),
-        },
-        _ => 0,
+    if !print_solution(Task { day, part }, source, timeout) {
+        std::process::exit(1);
+    }
+}
+
+// prints the answer and how long it took, or why there isn't one, returning
+// whether the task was solved
+fn print_solution(task: Task, source: &InputSource, timeout: Option<Duration>) -> bool {
+    let result = runner::run(task, source, timeout);
+    match result.outcome {
+        Outcome::Solved(answer) => {
+            println!("{}", answer);
+            println!("Took {:?} us", result.elapsed.as_micros());
+            if let Some(m) = result.memory {
+                println!(
+                    "Allocated {} in {} allocations, peak {}",
+                    memstats::format_bytes(m.bytes),
+                    m.allocations,
+                    memstats::format_bytes(m.peak)
+                );
+            }
+            return true;
+        }
+        Outcome::Unsolved => eprintln!("Not solved yet"),
+        Outcome::Failed(msg) => eprintln!("Failed: {}", msg),
+        Outcome::TimedOut(limit) => eprintln!("Timed out after {:?}", limit),
+        Outcome::Invalid(e) => {
+            let input = source.read(task.day, task.part).unwrap_or_default();
+            eprint!("{}", e.diagnostic(&source.name(task.day), &input));
+        }
+    }
+    false
+}
+
+fn fetch(days: &[u8]) {
+    let fetcher = match Fetcher::from_env() {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("Failed to fetch: {}", e);
+            std::process::exit(1);
+        }
     };
-    if result == 0 {
-        let result = match day {
-            9 => match part {
-                1 => day9::part_1(&input),
-                2 => day9::part_2(&input),
-                _ => panic!("Invalid part"),
-            },
-            _ => 0,
-        };
-        println!("{:?}", result);
-        println!("Took {:?} us", start.elapsed().as_micros());
+    let mut failed = false;
+    for day in days {
+        match fetcher.fetch(*day) {
+            Ok(Fetched::Downloaded(path)) => println!("Day {}: saved to {}", day, path.display()),
+            Ok(Fetched::Cached(path)) => {
+                println!("Day {}: already saved in {}", day, path.display())
+            }
+            Err(e) => {
+                eprintln!("Day {}: {}", day, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/**
+ * Solves a part and submits the answer, unless earlier guesses already show
+ * it's wrong. Every guess the server judges is added to the history, and a
+ * correct one is also recorded as the expected answer for verify.
+ */
+fn submit(task: Task, source: &InputSource, timeout: Option<Duration>) {
+    println!("Day {:?} Part {:?} : ", task.day, task.part);
+    let result = runner::run(task, source, timeout);
+    let answer = match submit::answer_to_submit(&result.outcome) {
+        Ok(answer) => answer,
+        Err(reason) => {
+            eprintln!("Not submitting: {}", reason);
+            std::process::exit(1);
+        }
+    };
+    println!("{}", answer);
+
+    let history_path = submit::history_path();
+    let mut history = match History::load(&history_path) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("Failed to load guess history: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let guess = answer.to_string();
+    match history.check(task, &guess) {
+        Check::Fresh => {}
+        Check::Repeat(Response::Correct) => {
+            println!("{} was already accepted", guess);
+            return;
+        }
+        Check::Repeat(response) => {
+            eprintln!(
+                "Not submitting: {} was already submitted and was {}",
+                guess, response
+            );
+            std::process::exit(1);
+        }
+        Check::AlreadySolved(correct) => {
+            eprintln!(
+                "Not submitting: this part was already solved with {}",
+                correct
+            );
+            std::process::exit(1);
+        }
+        Check::OutOfBounds(reason) => {
+            eprintln!("Warning: {}", reason);
+            if !confirm("Submit anyway?") {
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let response = Fetcher::from_env()
+        .and_then(|f| f.post_answer(task.day, task.part, &guess))
+        .map(|page| Response::parse(&page));
+    let response = match response {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Failed to submit: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("The answer is {}", response);
+    history.record(task, &guess, &response);
+    if let Err(e) = history.save(&history_path) {
+        eprintln!(
+            "Failed to save guess history to {}: {}",
+            history_path.display(),
+            e
+        );
+    }
+    if response == Response::Correct {
+        record_answer(task, answer);
     } else {
-        println!("{:?}", result);
-        println!("Took {:?} us", start.elapsed().as_micros());
+        std::process::exit(1);
+    }
+}
+
+fn record_answer(task: Task, answer: &Answer) {
+    let path = answers::answers_path();
+    let saved = Answers::load(&path).and_then(|mut answers| {
+        answers.set(task.day, task.part, answer);
+        answers.save(&path).map_err(|e| e.to_string())
+    });
+    if let Err(e) = saved {
+        eprintln!("Failed to record the answer in {}: {}", path.display(), e);
+    }
+}
+
+// how often watch checks the files for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/**
+ * Runs a part, then runs it again whenever its input file or its day's source
+ * changes, until interrupted. Input changes are picked up in process. Source
+ * changes need a rebuild, after which every run is done by the rebuilt binary.
+ */
+fn watch(task: Task, source: &InputSource, timeout: Option<Duration>) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source_file = root.join(format!("src/day{}.rs", task.day));
+    let mut files = vec![source_file.clone()];
+    files.extend(source.path(task.day));
+    let names: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+    println!("Watching {} (Ctrl-C to stop)", names.join(" and "));
+
+    let mut watcher = Watcher::new(files);
+    // taken before cargo gets a chance to replace it
+    let exe = env::current_exe();
+    let mut rebuilt = false;
+    let mut stale = false;
+    loop {
+        if stale {
+            println!("Not running, the last build failed");
+        } else if rebuilt {
+            match &exe {
+                Ok(exe) => run_rebuilt(exe, task, source, timeout),
+                Err(e) => eprintln!("Could not find the rebuilt binary: {}", e),
+            }
+        } else {
+            println!("Day {:?} Part {:?} : ", task.day, task.part);
+            print_solution(task, source, timeout);
+        }
+        let changed = watcher.wait(WATCH_INTERVAL);
+        for file in &changed {
+            println!("\n{} changed", file.display());
+        }
+        if changed.contains(&source_file) {
+            stale = !rebuild(root);
+            rebuilt |= !stale;
+        }
+    }
+}
+
+// builds the crate with the same profile as this binary, returning whether it
+// succeeded. Compiler errors go straight to the terminal.
+fn rebuild(root: &Path) -> bool {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = process::Command::new(cargo);
+    command.current_dir(root).args([
+        "build", "--quiet",
+    ]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    match command.status() {
+        Ok(status) => status.success(),
+        Err(e) => {
+            eprintln!("Could not run cargo build: {}", e);
+            false
+        }
+    }
+}
+
+// solves the task with a fresh copy of the binary, passing on the options that
+// affect a single run
+fn run_rebuilt(exe: &Path, task: Task, source: &InputSource, timeout: Option<Duration>) {
+    let mut args = vec![
+        task.day.to_string(),
+        task.part.to_string(),
+    ];
+    match source {
+        InputSource::File(path) => args.push(format!("--input={}", path.display())),
+        InputSource::Example(n) => args.push(format!("--example={}", n)),
+        InputSource::Default | InputSource::Stdin => {}
+    }
+    if let Some(limit) = timeout {
+        args.push(format!("--timeout={}", limit.as_secs_f64()));
+    }
+    if memstats::is_enabled() {
+        args.push(String::from("--mem"));
+    }
+    match log::level() {
+        Level::Quiet => {}
+        level => args.push(format!("-{}", "v".repeat(level as usize))),
+    }
+    if let Err(e) = process::Command::new(exe).args(&args).status() {
+        eprintln!("Could not run {}: {}", exe.display(), e);
+    }
+}
+
+fn verify(
+    days: &[u8],
+    part: Option<u8>,
+    record: bool,
+    source: &InputSource,
+    jobs: usize,
+    timeout: Option<Duration>,
+) {
+    if let InputSource::Example(_) = source {
+        return verify_examples(days, part);
+    }
+    let path = answers::answers_path();
+    let mut answers = match Answers::load(&path) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Failed to load answers: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let results = runner::run_all(&runner::tasks_for(days, part), source, jobs, timeout);
+    let verdicts: Vec<Verdict> = results.iter().map(|r| answers.check(r)).collect();
+    print!("{}", report::verification_table(&results, &verdicts));
+
+    // offer to record any answers we didn't know about yet, but only for the saved
+    // puzzle input as that's what answers.toml describes
+    let recordable = !matches!(source, InputSource::File(_) | InputSource::Stdin);
+    let mut changed = false;
+    for (result, verdict) in results.iter().zip(&verdicts).filter(|_| recordable) {
+        if let (Verdict::Unknown, Outcome::Solved(answer)) = (verdict, &result.outcome) {
+            let question = format!(
+                "Record {} as the answer for day {} part {}?",
+                answer, result.task.day, result.task.part
+            );
+            if record || confirm(&question) {
+                answers.set(result.task.day, result.task.part, answer);
+                changed = true;
+            }
+        }
+    }
+    if changed {
+        match answers.save(&path) {
+            Ok(_) => println!("Saved answers to {}", path.display()),
+            Err(e) => eprintln!("Failed to save answers to {}: {}", path.display(), e),
+        }
+    }
+    if verdicts.iter().any(|v| matches!(v, Verdict::Fail { .. } | Verdict::Error(_))) {
+        std::process::exit(1);
+    }
+}
+
+// checks every registered example rather than the puzzle input, so there's
+// nothing to record
+fn verify_examples(days: &[u8], part: Option<u8>) {
+    let (tasks, skipped): (Vec<Task>, Vec<Task>) = runner::tasks_for(days, part)
+        .into_iter()
+        .partition(|t| !solution::WORK_IN_PROGRESS.contains(&t.day));
+    let mut skipped_days: Vec<u8> = skipped.iter().map(|t| t.day).collect();
+    skipped_days.dedup();
+    for day in skipped_days {
+        eprintln!(
+            "Skipping day {}, its solver is still a work in progress",
+            day
+        );
+    }
+    let checked: Vec<(RunResult, &str)> =
+        runner::quietly(|| tasks.into_iter().flat_map(runner::run_examples).collect());
+    let verdicts: Vec<Verdict> = checked.iter().map(|(r, e)| Verdict::of(r, Some(e))).collect();
+    let results: Vec<RunResult> = checked.into_iter().map(|(r, _)| r).collect();
+    print!("{}", report::verification_table(&results, &verdicts));
+    if verdicts.iter().any(|v| !matches!(v, Verdict::Pass | Verdict::Unsolved)) {
+        std::process::exit(1);
+    }
+}
+
+fn bench(days: &[u8], part: Option<u8>, options: &BenchOptions, source: &InputSource) {
+    let path = bench::baseline_path();
+    // the baseline was timed on the saved puzzle input, so examples and other inputs
+    // aren't compared to it
+    let mut baseline = match source {
+        InputSource::Default => match Baseline::load(&path) {
+            Ok(b) => b,
+            Err(e) => {
+                eprintln!("Failed to load benchmark baseline: {}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => Baseline::default(),
+    };
+    let tasks = runner::tasks_for(days, part);
+    let results: Vec<bench::BenchResult> = runner::quietly(|| {
+        tasks
+            .iter()
+            .map(|t| bench::bench(*t, source, options.warmup, options.runs))
+            .collect()
+    });
+    print!(
+        "{}",
+        report::bench_table(&results, &baseline, options.threshold)
+    );
+    let regressions = results
+        .iter()
+        .filter(|r| bench::is_regression(r, &baseline, options.threshold))
+        .count();
+
+    if options.save {
+        baseline.update(&results);
+        match baseline.save(&path) {
+            Ok(_) => println!("Saved baseline to {}", path.display()),
+            Err(e) => eprintln!("Failed to save baseline to {}: {}", path.display(), e),
+        }
+    }
+    if regressions > 0 {
+        println!(
+            "{} regression(s) slower than the baseline by more than {}%",
+            regressions, options.threshold
+        );
+        std::process::exit(1);
+    }
+}
+
+// asks a yes/no question on the terminal, defaulting to no when not interactive
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{} [y/N] ", question);
+    io::stdout().flush().ok();
+    let mut reply = String::new();
+    match io::stdin().lock().read_line(&mut reply) {
+        Ok(_) => reply.trim().eq_ignore_ascii_case("y"),
+        Err(_) => false,
     }
 }
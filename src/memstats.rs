@@ -0,0 +1,162 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/**
+ * A global allocator that counts allocations made by each thread once enabled.
+ * The binary installs it with
+ *
+ *  #[global_allocator]
+ *  static ALLOCATOR: CountingAllocator = CountingAllocator;
+ *
+ * Until `enable` is called it only costs an atomic load per call. Counts are
+ * kept per thread so solvers running on other threads don't skew each other.
+ */
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    // memory freed on another thread than allocated it can take this below 0
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // try_with as the thread local may already be gone while a thread exits
+    let _ = COUNTS.try_with(|c| {
+        let mut counts = c.get();
+        if allocated > 0 {
+            counts.allocations += 1;
+            counts.bytes += allocated as u64;
+        }
+        counts.live += allocated as i64 - freed as i64;
+        counts.peak = counts.peak.max(counts.live);
+        c.set(counts);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    // counted as a new allocation of the new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/**
+ * What a piece of code allocated: the number of allocations, the total bytes
+ * they asked for and the most bytes it had live at once
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+/**
+ * Runs `f`, returning what it allocated on this thread if counting is enabled
+ */
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<MemStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+    let before = COUNTS.with(|c| {
+        let mut counts = c.get();
+        counts.peak = counts.live;
+        c.set(counts);
+        counts
+    });
+    let result = f();
+    let after = COUNTS.with(|c| c.get());
+    let stats = MemStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+/**
+ * Formats a byte count with a binary unit, e.g. 1.5 KiB
+ */
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = [
+        "B", "KiB", "MiB", "GiB",
+    ];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,208 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    input::{Input, InputSource},
+    runner::{self, Outcome, Task},
+    solution::{self, Solution},
+};
+
+/**
+ * A day's parsed model, as seen from the REPL. Days opt in through
+ * `Solution::inspect` and add their own commands on top of the built in ones.
+ */
+pub trait Inspect {
+    /**
+     * The day's commands as (usage, description) pairs, listed by `help`
+     */
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /**
+     * Runs one of the day's commands, returning None if it isn't one of them
+     */
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>>;
+}
+
+const BUILT_IN: &[(&str, &str)] = &[
+    ("part1, part2", "solve a part against the loaded input"),
+    ("reload", "read and parse the input again"),
+    ("help", "list the commands"),
+    ("quit", "leave the REPL"),
+];
+
+/**
+ * A day's input and the model parsed from it
+ */
+pub struct Session {
+    solution: &'static dyn Solution,
+    source: InputSource,
+    input: Input,
+    model: Option<Box<dyn Inspect>>,
+}
+
+impl Session {
+    /**
+     * Reads a day's input and parses it into the day's model, if it has one
+     */
+    pub fn load(day: u8, source: &InputSource) -> Result<Session, String> {
+        let solution =
+            solution::find(day).ok_or_else(|| format!("no solution registered for day {}", day))?;
+        // the part only matters for picking an example
+        let input = source.read(day, 1)?;
+        let model =
+            solution.inspect(&input).map_err(|e| e.diagnostic(&source.name(day), &input))?;
+        Ok(Session {
+            solution,
+            source: source.clone(),
+            input,
+            model,
+        })
+    }
+
+    pub fn day(&self) -> u8 {
+        self.solution.day()
+    }
+
+    /**
+     * Runs a line of input, returning what to print, or None to quit
+     */
+    pub fn execute(&mut self, line: &str) -> Option<String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Some(String::new()),
+        };
+        let output = match command {
+            "quit" | "exit" => return None,
+            "help" => self.help(),
+            "part1" | "part2" => self.solve(if command == "part1" { 1 } else { 2 }),
+            "reload" => match Session::load(self.day(), &self.source) {
+                Ok(session) => {
+                    *self = session;
+                    format!("Reloaded {} lines", self.input.len())
+                }
+                Err(e) => format!("Reload failed, keeping the old input: {}", e),
+            },
+            _ => match self.model.as_ref().and_then(|m| m.run(command, args)) {
+                Some(Ok(output)) => output,
+                Some(Err(e)) => format!("error: {}", e),
+                None => format!("Unknown command {:?}, try help", command),
+            },
+        };
+        Some(output)
+    }
+
+    fn help(&self) -> String {
+        let day_commands = self.model.as_ref().map_or(&[][..], |m| m.commands());
+        let width = BUILT_IN.iter().chain(day_commands).map(|(u, _)| u.len()).max().unwrap_or(0);
+        let list = |commands: &[(&str, &str)]| -> String {
+            commands
+                .iter()
+                .map(|(u, d)| format!("  {:<w$}  {}\n", u, d, w = width))
+                .collect()
+        };
+        let mut out = list(BUILT_IN);
+        match self.model {
+            Some(_) => out += &format!("Day {}:\n{}", self.day(), list(day_commands)),
+            None => out += &format!("Day {} has no inspection commands\n", self.day()),
+        }
+        out.trim_end().to_string()
+    }
+
+    fn solve(&self, part: u8) -> String {
+        let task = Task {
+            day: self.day(),
+            part,
+        };
+        let result = runner::quietly(|| runner::solve(task, self.solution, &self.input));
+        match result.outcome {
+            Outcome::Solved(answer) => {
+                format!("{} ({} us)", answer, result.elapsed.as_micros())
+            }
+            Outcome::Unsolved => String::from("not solved yet"),
+            outcome => format!("error: {}", outcome.error().unwrap_or_default()),
+        }
+    }
+}
+
+/**
+ * Reads commands from `input` until it ends or the session is quit, writing a
+ * prompt before each and the output after it
+ */
+pub fn run(
+    session: &mut Session,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    loop {
+        write!(output, "day{}> ", session.day())?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        match session.execute(&line) {
+            Some(out) if out.is_empty() => {}
+            Some(out) => writeln!(output, "{}", out)?,
+            None => return Ok(()),
+        }
+    }
+}
+
+/**
+ * Parses a command argument, naming it in the error
+ */
+pub fn arg<T: std::str::FromStr>(args: &[&str], i: usize, name: &str) -> Result<T, String> {
+    let value = args.get(i).ok_or_else(|| format!("missing {}", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("{} should be a number, got {:?}", name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the answer without the timing
+    fn answer(session: &mut Session, command: &str) -> String {
+        let output = session.execute(command).unwrap();
+        output.split(" (").next().unwrap().to_string()
+    }
+
+    #[test]
+    pub fn test_built_in_commands() {
+        let mut session = Session::load(9, &InputSource::Example(1)).unwrap();
+        assert_eq!(answer(&mut session, "part1"), "114");
+        assert_eq!(answer(&mut session, "  part2 "), "2");
+        assert_eq!(session.execute(""), Some(String::new()));
+        assert!(session.execute("help").unwrap().contains("has no inspection commands"));
+        assert!(session.execute("bogus").unwrap().starts_with("Unknown command"));
+        assert_eq!(
+            session.execute("reload"),
+            Some(String::from("Reloaded 3 lines"))
+        );
+        assert_eq!(session.execute("quit"), None);
+    }
+
+    #[test]
+    pub fn test_run() {
+        let mut session = Session::load(9, &InputSource::Example(1)).unwrap();
+        let mut output = Vec::new();
+        run(&mut session, "part1\nexit\npart2\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("day9> 114 ("));
+        assert!(output.ends_with("day9> "));
+    }
+
+    #[test]
+    pub fn test_arg() {
+        assert_eq!(arg::<usize>(&["3", "x"], 0, "row"), Ok(3));
+        assert_eq!(
+            arg::<usize>(&["3", "x"], 1, "col"),
+            Err(String::from("col should be a number, got \"x\""))
+        );
+        assert_eq!(
+            arg::<usize>(&[], 0, "row"),
+            Err(String::from("missing row"))
+        );
+    }
+}
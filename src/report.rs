@@ -0,0 +1,558 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{
+    answers::Verdict,
+    bench::{self, Baseline, BenchResult},
+    memstats::{self, MemStats},
+    runner::{Outcome, RunResult},
+};
+
+/**
+ * How run results are printed: a table for people, or JSON or CSV records for
+ * scripts
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/**
+ * Formats results in the given format
+ */
+pub fn results(results: &[RunResult], format: Format) -> String {
+    match format {
+        Format::Text => table(results),
+        Format::Json => json(results),
+        Format::Csv => csv(results),
+    }
+}
+
+/**
+ * Formats results as a table of day, part, answer and elapsed time, with the
+ * allocations and bytes allocated and peak memory use if they were counted
+ */
+pub fn table(results: &[RunResult]) -> String {
+    let answers: Vec<String> = results.iter().map(answer_cell).collect();
+    let answer_width = answers.iter().map(|a| a.len()).max().unwrap_or(0).max("Answer".len());
+    let with_memory = results.iter().any(|r| r.memory.is_some());
+
+    let mut out = format!(
+        "{:>3}  {:>4}  {:<w$}  {:>10}",
+        "Day",
+        "Part",
+        "Answer",
+        "Time (us)",
+        w = answer_width
+    );
+    if with_memory {
+        out += &format!("  {:>10}  {:>10}  {:>10}", "Allocs", "Bytes", "Peak");
+    }
+    out.push('\n');
+    for (result, answer) in results.iter().zip(answers) {
+        let elapsed = match result.outcome {
+            Outcome::Solved(_) => result.elapsed.as_micros().to_string(),
+            _ => String::from("-"),
+        };
+        let mut line = format!(
+            "{:>3}  {:>4}  {:<w$}  {:>10}",
+            result.task.day,
+            result.task.part,
+            answer,
+            elapsed,
+            w = answer_width
+        );
+        if with_memory {
+            let (allocations, bytes, peak) = match result.memory {
+                Some(m) => (
+                    m.allocations.to_string(),
+                    memstats::format_bytes(m.bytes),
+                    memstats::format_bytes(m.peak),
+                ),
+                None => (String::from("-"), String::from("-"), String::from("-")),
+            };
+            line += &format!("  {:>10}  {:>10}  {:>10}", allocations, bytes, peak);
+        }
+        out += &line;
+        out.push('\n');
+    }
+    let unsolved = results.iter().filter(|r| r.outcome == Outcome::Unsolved).count();
+    let failures = results.iter().filter(|r| r.outcome.error().is_some()).count() - unsolved;
+    out += &format!("{} run, {} failed", results.len(), failures);
+    if unsolved > 0 {
+        out += &format!(", {} unsolved", unsolved);
+    }
+    out.push('\n');
+    out
+}
+
+/**
+ * Formats verification verdicts as a table of day, part, status and answers
+ */
+pub fn verification_table(results: &[RunResult], verdicts: &[Verdict]) -> String {
+    let answers: Vec<String> = results.iter().map(answer_cell).collect();
+    let answer_width = answers.iter().map(|a| a.len()).max().unwrap_or(0).max("Answer".len());
+
+    let mut out = format!(
+        "{:>3}  {:>4}  {:<8}  {:<w$}  {}\n",
+        "Day",
+        "Part",
+        "Status",
+        "Answer",
+        "Expected",
+        w = answer_width
+    );
+    let mut counts = [0; 5];
+    for ((result, verdict), answer) in results.iter().zip(verdicts).zip(answers) {
+        let (status, expected, count_idx) = match verdict {
+            Verdict::Pass => ("pass", "", 0),
+            Verdict::Fail { expected } => ("FAIL", expected.as_str(), 1),
+            Verdict::Unknown => ("unknown", "", 2),
+            Verdict::Unsolved => ("unsolved", "", 3),
+            Verdict::Error(_) => ("ERROR", "", 4),
+        };
+        counts[count_idx] += 1;
+        let line = format!(
+            "{:>3}  {:>4}  {:<8}  {:<w$}  {}",
+            result.task.day,
+            result.task.part,
+            status,
+            answer,
+            expected,
+            w = answer_width
+        );
+        out += line.trim_end();
+        out.push('\n');
+    }
+    out += &format!(
+        "{} passed, {} failed, {} unknown, {} unsolved, {} errors\n",
+        counts[0], counts[1], counts[2], counts[3], counts[4]
+    );
+    out
+}
+
+/**
+ * Formats benchmark statistics, comparing medians against the baseline, with
+ * the allocations and peak memory use of a run if they were counted
+ */
+pub fn bench_table(results: &[BenchResult], baseline: &Baseline, threshold: f64) -> String {
+    let with_memory = results.iter().any(|r| r.memory.is_some());
+    let mut out = format!(
+        "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Day", "Part", "Min (us)", "Median", "Mean", "P95"
+    );
+    if with_memory {
+        out += &format!("  {:>10}  {:>10}", "Allocs", "Peak");
+    }
+    out += &format!("  {:>10}  {}\n", "Baseline", "Change");
+    for result in results {
+        let stats = match &result.stats {
+            Ok(s) => s,
+            Err(Outcome::Unsolved) => {
+                out += &format!(
+                    "{:>3}  {:>4}  unsolved\n",
+                    result.task.day, result.task.part
+                );
+                continue;
+            }
+            Err(outcome) => {
+                out += &format!(
+                    "{:>3}  {:>4}  FAILED: {}\n",
+                    result.task.day,
+                    result.task.part,
+                    outcome.error().unwrap_or_default()
+                );
+                continue;
+            }
+        };
+        let (base, change) = match baseline.get(result.task) {
+            Some(base) => {
+                let pct = bench::change_percent(base, stats.median);
+                let flag = match bench::is_regression(result, baseline, threshold) {
+                    true => "  REGRESSION",
+                    false => "",
+                };
+                (micros(base), format!("{:+.1}%{}", pct, flag))
+            }
+            None => (String::from("-"), String::new()),
+        };
+        let mut line = format!(
+            "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>10}",
+            result.task.day,
+            result.task.part,
+            micros(stats.min),
+            micros(stats.median),
+            micros(stats.mean),
+            micros(stats.p95),
+        );
+        if with_memory {
+            let (allocations, peak) = match result.memory {
+                Some(m) => (m.allocations.to_string(), memstats::format_bytes(m.peak)),
+                None => (String::from("-"), String::from("-")),
+            };
+            line += &format!("  {:>10}  {:>10}", allocations, peak);
+        }
+        line += &format!("  {:>10}  {}", base, change);
+        out += line.trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+/**
+ * Formats results as a JSON array with one record per result. Answers are
+ * always strings so large numbers survive parsers that use doubles. The memory
+ * fields are null unless allocations were counted.
+ *
+ *  [
+ *    {"day": 1, "part": 1, "answer": "142", "elapsed_us": 12, "status": "solved", "error": null,
+ *     "allocations": 3, "bytes": 96, "peak_bytes": 64}
+ *  ]
+ */
+pub fn json(results: &[RunResult]) -> String {
+    let records: Vec<String> = results
+        .iter()
+        .map(|r| {
+            let (answer, error) = match &r.outcome {
+                Outcome::Solved(a) => (json_string(&a.to_string()), String::from("null")),
+                outcome => (
+                    String::from("null"),
+                    json_string(&outcome.error().unwrap_or_default()),
+                ),
+            };
+            let [allocations, bytes, peak] = memory_fields(r.memory, "null");
+            format!(
+                r#"  {{"day": {}, "part": {}, "answer": {}, "elapsed_us": {}, "status": "{}", "error": {}, "allocations": {}, "bytes": {}, "peak_bytes": {}}}"#,
+                r.task.day,
+                r.task.part,
+                answer,
+                r.elapsed.as_micros(),
+                status(&r.outcome),
+                error,
+                allocations,
+                bytes,
+                peak
+            )
+        })
+        .collect();
+    match records.is_empty() {
+        true => String::from("[]\n"),
+        false => format!("[\n{}\n]\n", records.join(",\n")),
+    }
+}
+
+/**
+ * Formats results as CSV with a header row
+ */
+pub fn csv(results: &[RunResult]) -> String {
+    let mut out =
+        String::from("day,part,answer,elapsed_us,status,error,allocations,bytes,peak_bytes\n");
+    for r in results {
+        let (answer, error) = match &r.outcome {
+            Outcome::Solved(a) => (a.to_string(), String::new()),
+            outcome => (String::new(), outcome.error().unwrap_or_default()),
+        };
+        let [allocations, bytes, peak] = memory_fields(r.memory, "");
+        out += &format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            r.task.day,
+            r.task.part,
+            csv_field(&answer),
+            r.elapsed.as_micros(),
+            status(&r.outcome),
+            csv_field(&error),
+            allocations,
+            bytes,
+            peak
+        );
+    }
+    out
+}
+
+fn status(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Solved(_) => "solved",
+        Outcome::Unsolved => "unsolved",
+        Outcome::Failed(_) => "failed",
+        Outcome::Invalid(_) => "invalid",
+        Outcome::TimedOut(_) => "timeout",
+    }
+}
+
+// allocations, bytes and peak bytes as raw numbers, or `missing` for each
+fn memory_fields(memory: Option<MemStats>, missing: &str) -> [String; 3] {
+    match memory {
+        Some(m) => [
+            m.allocations.to_string(),
+            m.bytes.to_string(),
+            m.peak.to_string(),
+        ],
+        None => [
+            missing.to_string(),
+            missing.to_string(),
+            missing.to_string(),
+        ],
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// quotes a field if it contains anything CSV treats specially
+fn csv_field(s: &str) -> String {
+    match s.contains([
+        ',', '"', '\n', '\r',
+    ]) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+fn micros(d: Duration) -> String {
+    format!("{:.1}", d.as_nanos() as f64 / 1000.0)
+}
+
+fn answer_cell(result: &RunResult) -> String {
+    match &result.outcome {
+        Outcome::Solved(a) => a.to_string(),
+        Outcome::Unsolved => String::from("unsolved"),
+        outcome => format!("FAILED: {}", outcome.error().unwrap_or_default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bench::Stats, runner::Task};
+
+    #[test]
+    pub fn test_table() {
+        let results = vec![
+            RunResult {
+                task: Task { day: 1, part: 1 },
+                outcome: Outcome::Solved(142_u64.into()),
+                elapsed: Duration::from_micros(12),
+                memory: None,
+            },
+            RunResult {
+                task: Task { day: 1, part: 2 },
+                outcome: Outcome::Failed(String::from("boom")),
+                elapsed: Duration::ZERO,
+                memory: None,
+            },
+        ];
+        let expected = "\
+Day  Part  Answer         Time (us)
+  1     1  142                   12
+  1     2  FAILED: boom           -
+2 run, 1 failed
+";
+        assert_eq!(table(&results), expected);
+    }
+
+    #[test]
+    pub fn test_table_with_memory() {
+        let expected = "\
+Day  Part  Answer                       Time (us)      Allocs       Bytes        Peak
+  1     1  142                                 12           -           -           -
+  1     2  FAILED: input \"x\", missing           -           3        96 B        64 B
+2 run, 1 failed
+";
+        assert_eq!(table(&mixed_results()), expected);
+    }
+
+    fn mixed_results() -> Vec<RunResult> {
+        vec![
+            RunResult {
+                task: Task { day: 1, part: 1 },
+                outcome: Outcome::Solved(142_u64.into()),
+                elapsed: Duration::from_micros(12),
+                memory: None,
+            },
+            RunResult {
+                task: Task { day: 1, part: 2 },
+                outcome: Outcome::Failed(String::from("input \"x\", missing")),
+                elapsed: Duration::ZERO,
+                memory: Some(MemStats {
+                    allocations: 3,
+                    bytes: 96,
+                    peak: 64,
+                }),
+            },
+        ]
+    }
+
+    #[test]
+    pub fn test_json() {
+        let expected = r#"[
+  {"day": 1, "part": 1, "answer": "142", "elapsed_us": 12, "status": "solved", "error": null, "allocations": null, "bytes": null, "peak_bytes": null},
+  {"day": 1, "part": 2, "answer": null, "elapsed_us": 0, "status": "failed", "error": "input \"x\", missing", "allocations": 3, "bytes": 96, "peak_bytes": 64}
+]
+"#;
+        assert_eq!(json(&mixed_results()), expected);
+        assert_eq!(json(&[]), "[]\n");
+        assert_eq!(json_string("a\nb\u{1}"), "\"a\\nb\\u0001\"");
+    }
+
+    #[test]
+    pub fn test_csv() {
+        let expected = "\
+day,part,answer,elapsed_us,status,error,allocations,bytes,peak_bytes
+1,1,142,12,solved,,,,
+1,2,,0,failed,\"input \"\"x\"\", missing\",3,96,64
+";
+        assert_eq!(csv(&mixed_results()), expected);
+    }
+
+    #[test]
+    pub fn test_unsolved() {
+        let results = vec![
+            RunResult {
+                task: Task { day: 16, part: 1 },
+                outcome: Outcome::Unsolved,
+                elapsed: Duration::from_micros(1),
+                memory: None,
+            },
+        ];
+        let expected = "\
+Day  Part  Answer     Time (us)
+ 16     1  unsolved           -
+1 run, 0 failed, 1 unsolved
+";
+        assert_eq!(table(&results), expected);
+        assert!(json(&results).contains(r#""answer": null, "elapsed_us": 1, "status": "unsolved""#));
+        assert!(csv(&results).ends_with("16,1,,1,unsolved,not solved yet,,,\n"));
+        let results = vec![
+            BenchResult {
+                task: Task { day: 16, part: 1 },
+                stats: Err(Outcome::Unsolved),
+                memory: None,
+            },
+        ];
+        let baseline = Baseline::parse("16 1 100").unwrap();
+        assert!(bench_table(&results, &baseline, 10.0).ends_with("\n 16     1  unsolved\n"));
+    }
+
+    #[test]
+    pub fn test_format_from_str() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    pub fn test_verification_table() {
+        let results = vec![
+            RunResult {
+                task: Task { day: 2, part: 1 },
+                outcome: Outcome::Solved(8_u64.into()),
+                elapsed: Duration::ZERO,
+                memory: None,
+            },
+            RunResult {
+                task: Task { day: 2, part: 2 },
+                outcome: Outcome::Solved(5_u64.into()),
+                elapsed: Duration::ZERO,
+                memory: None,
+            },
+            RunResult {
+                task: Task { day: 3, part: 1 },
+                outcome: Outcome::Unsolved,
+                elapsed: Duration::ZERO,
+                memory: None,
+            },
+        ];
+        let verdicts = vec![
+            Verdict::Pass,
+            Verdict::Fail {
+                expected: String::from("2286"),
+            },
+            Verdict::Unsolved,
+        ];
+        let expected = "\
+Day  Part  Status    Answer    Expected
+  2     1  pass      8
+  2     2  FAIL      5         2286
+  3     1  unsolved  unsolved
+1 passed, 1 failed, 0 unknown, 1 unsolved, 0 errors
+";
+        assert_eq!(verification_table(&results, &verdicts), expected);
+    }
+
+    #[test]
+    pub fn test_bench_table() {
+        let samples: Vec<Duration> =
+            [10, 20, 30].iter().map(|m| Duration::from_micros(*m)).collect();
+        let results = vec![
+            BenchResult {
+                task: Task { day: 4, part: 1 },
+                stats: Ok(Stats::from_samples(&samples).unwrap()),
+                memory: None,
+            },
+            BenchResult {
+                task: Task { day: 4, part: 2 },
+                stats: Err(Outcome::Failed(String::from("boom"))),
+                memory: None,
+            },
+        ];
+        let baseline = Baseline::parse("4 1 15000").unwrap();
+        let expected = "\
+Day  Part    Min (us)      Median        Mean         P95    Baseline  Change
+  4     1        10.0        20.0        20.0        30.0        15.0  +33.3%  REGRESSION
+  4     2  FAILED: boom
+";
+        assert_eq!(bench_table(&results, &baseline, 10.0), expected);
+    }
+
+    #[test]
+    pub fn test_bench_table_with_memory() {
+        let samples = [Duration::from_micros(10)];
+        let results = vec![
+            BenchResult {
+                task: Task { day: 4, part: 1 },
+                stats: Ok(Stats::from_samples(&samples).unwrap()),
+                memory: Some(MemStats {
+                    allocations: 12,
+                    bytes: 4096,
+                    peak: 2048,
+                }),
+            },
+        ];
+        let expected = "\
+Day  Part    Min (us)      Median        Mean         P95      Allocs        Peak    Baseline  Change
+  4     1        10.0        10.0        10.0        10.0          12     2.0 KiB           -
+";
+        assert_eq!(bench_table(&results, &Baseline::default(), 10.0), expected);
+    }
+}
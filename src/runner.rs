@@ -0,0 +1,337 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cancel::{self, CancelToken, Cancelled},
+    error::ParseError,
+    input::{Input, InputSource},
+    memstats::{self, MemStats},
+    solution::{self, Answer, Solution},
+};
+
+/**
+ * A single puzzle part to run
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Task {
+    pub day: u8,
+    pub part: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    // the part has no solver yet, so there's no answer to report
+    Unsolved,
+    Failed(String),
+    // the solver rejected its input
+    Invalid(ParseError),
+    // the solver was still running when its time limit was up
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    /**
+     * Why the task didn't produce an answer, if it didn't
+     */
+    pub fn error(&self) -> Option<String> {
+        match self {
+            Outcome::Solved(_) => None,
+            Outcome::Unsolved => Some(String::from("not solved yet")),
+            Outcome::Failed(msg) => Some(msg.clone()),
+            Outcome::Invalid(e) => Some(format!("invalid input: {}", e)),
+            Outcome::TimedOut(limit) => Some(format!("timed out after {:?}", limit)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub task: Task,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    // only measured when allocation counting is enabled
+    pub memory: Option<MemStats>,
+}
+
+/**
+ * Builds the tasks for every registered day in `days`, optionally limited to a
+ * single part. Days without a registered solution are skipped.
+ */
+pub fn tasks_for(days: &[u8], part: Option<u8>) -> Vec<Task> {
+    let parts: Vec<u8> = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    days.iter()
+        .filter(|d| solution::find(**d).is_some())
+        .flat_map(|d| parts.iter().map(|p| Task { day: *d, part: *p }))
+        .collect()
+}
+
+// how long a cancelled solver gets to reach a checkpoint before it's abandoned
+const CANCEL_GRACE: Duration = Duration::from_millis(100);
+
+/**
+ * Runs a single task, giving up on it after `timeout` if there is one. Missing
+ * solutions, missing input and panicking solvers are all reported as a failed
+ * outcome rather than aborting the caller.
+ */
+pub fn run(task: Task, source: &InputSource, timeout: Option<Duration>) -> RunResult {
+    match load(task, source) {
+        Ok((solution, input)) => match timeout {
+            Some(limit) => solve_with_timeout(task, solution, input, limit),
+            None => solve(task, solution, &input),
+        },
+        Err(msg) => RunResult {
+            task,
+            outcome: Outcome::Failed(msg),
+            elapsed: Duration::ZERO,
+            memory: None,
+        },
+    }
+}
+
+/**
+ * Looks up the solution for a task and reads its input
+ */
+pub fn load(task: Task, source: &InputSource) -> Result<(&'static dyn Solution, Input), String> {
+    let solution = solution::find(task.day)
+        .ok_or_else(|| format!("no solution registered for day {}", task.day))?;
+    Ok((solution, source.read(task.day, task.part)?))
+}
+
+/**
+ * Times a single run of a solver against an already loaded input, counting its
+ * allocations if that's enabled
+ */
+pub fn solve(task: Task, solution: &dyn Solution, input: &Input) -> RunResult {
+    let ((result, elapsed), memory) = memstats::measure(|| {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| solution.solve(task.part, input)));
+        (result, start.elapsed())
+    });
+    let outcome = match result {
+        Ok(Some(Ok(Answer::Unsolved))) => Outcome::Unsolved,
+        Ok(Some(Ok(answer))) => Outcome::Solved(answer),
+        Ok(Some(Err(e))) => Outcome::Invalid(e),
+        Ok(None) => Outcome::Failed(format!("invalid part {}", task.part)),
+        Err(payload) if payload.is::<Cancelled>() => Outcome::Failed(String::from("cancelled")),
+        Err(payload) => Outcome::Failed(format!("panicked: {}", panic_message(&*payload))),
+    };
+    RunResult {
+        task,
+        outcome,
+        elapsed,
+        memory,
+    }
+}
+
+/**
+ * Solves a task on its own thread, waiting at most `limit` for the answer. A
+ * solver that runs over is cancelled, which stops it at its next checkpoint; one
+ * without checkpoints is left running in the background and abandoned.
+ */
+pub fn solve_with_timeout(
+    task: Task,
+    solution: &'static dyn Solution,
+    input: Input,
+    limit: Duration,
+) -> RunResult {
+    let token = CancelToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let solver_token = token.clone();
+    let spawned = thread::Builder::new()
+        .name(format!("day {} part {}", task.day, task.part))
+        .spawn(move || {
+            let result = cancel::with_token(solver_token, || solve(task, solution, &input));
+            // the runner may have stopped waiting
+            let _ = sender.send(result);
+        });
+    let outcome = match spawned.map(|_| receiver.recv_timeout(limit)) {
+        Ok(Ok(result)) => return result,
+        Ok(Err(RecvTimeoutError::Timeout)) => {
+            token.cancel();
+            let _ = receiver.recv_timeout(CANCEL_GRACE);
+            Outcome::TimedOut(limit)
+        }
+        Ok(Err(RecvTimeoutError::Disconnected)) => {
+            Outcome::Failed(String::from("solver thread exited without an answer"))
+        }
+        Err(e) => Outcome::Failed(format!("could not start solver thread: {}", e)),
+    };
+    RunResult {
+        task,
+        outcome,
+        elapsed: limit,
+        memory: None,
+    }
+}
+
+/**
+ * Runs a task against each of its day's examples for that part, pairing each
+ * result with the example's expected answer
+ */
+pub fn run_examples(task: Task) -> Vec<(RunResult, &'static str)> {
+    let solution = match solution::find(task.day) {
+        Some(s) => s,
+        None => return Vec::new(),
+    };
+    solution
+        .examples_for(task.part)
+        .iter()
+        .filter_map(|e| {
+            let expected = e.expected(task.part)?;
+            Some((solve(task, solution, &e.input()), expected))
+        })
+        .collect()
+}
+
+/**
+ * Runs every task, spread over `jobs` threads, returning the results in task
+ * order, with each task limited to `timeout` if there is one. Each task is timed
+ * on the thread that runs it. The default panic hook
+ * is silenced while running so a failing solver shows up as a row in the
+ * results instead of a stack trace in the middle of the output.
+ */
+pub fn run_all(
+    tasks: &[Task],
+    source: &InputSource,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Vec<RunResult> {
+    quietly(|| {
+        if jobs <= 1 {
+            return tasks.iter().map(|t| run(*t, source, timeout)).collect();
+        }
+        // workers take the next task off a shared index until there are none left
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, RunResult)> = thread::scope(|s| {
+            let workers: Vec<_> = (0..jobs.min(tasks.len()))
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            match tasks.get(i) {
+                                Some(task) => done.push((i, run(*task, source, timeout))),
+                                None => return done,
+                            }
+                        }
+                    })
+                })
+                .collect();
+            workers.into_iter().flat_map(|w| w.join().unwrap()).collect()
+        });
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, r)| r).collect()
+    })
+}
+
+/**
+ * Calls `f` with the default panic hook silenced, restoring it afterwards
+ */
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        return s.to_string();
+    }
+    if let Some(s) = payload.downcast_ref::<String>() {
+        return s.clone();
+    }
+    String::from("unknown panic")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day16;
+
+    #[test]
+    pub fn test_tasks_for_skips_unregistered_days() {
+        let tasks = tasks_for(&[15, 16, 17], None);
+        assert_eq!(
+            tasks,
+            vec![
+                Task { day: 15, part: 1 },
+                Task { day: 15, part: 2 },
+                Task { day: 16, part: 1 },
+                Task { day: 16, part: 2 },
+            ]
+        );
+        assert_eq!(tasks_for(&[3], Some(2)), vec![Task { day: 3, part: 2 }]);
+    }
+
+    #[test]
+    pub fn test_run_all_jobs_keeps_order() {
+        let tasks = tasks_for(&(1..=11).collect::<Vec<u8>>(), None);
+        let source = InputSource::Example(1);
+        let outcomes = |jobs| -> Vec<(Task, Outcome)> {
+            run_all(&tasks, &source, jobs, None)
+                .into_iter()
+                .map(|r| (r.task, r.outcome))
+                .collect()
+        };
+        let sequential = outcomes(1);
+        assert_eq!(sequential.len(), tasks.len());
+        assert_eq!(outcomes(4), sequential);
+    }
+
+    #[test]
+    pub fn test_stub_is_unsolved() {
+        let result = solve(Task { day: 16, part: 1 }, &day16::Day16, &Input::default());
+        assert_eq!(result.outcome, Outcome::Unsolved);
+        assert!(result.outcome.error().is_some());
+    }
+
+    #[test]
+    pub fn test_unregistered_day_fails() {
+        let result = run(Task { day: 30, part: 1 }, &InputSource::Default, None);
+        assert!(matches!(result.outcome, Outcome::Failed(_)));
+    }
+
+    struct Endless;
+
+    impl Solution for Endless {
+        fn day(&self) -> u8 {
+            30
+        }
+
+        fn part_1(&self, _input: &Input) -> Result<Answer, ParseError> {
+            loop {
+                cancel::checkpoint();
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+
+        fn part_2(&self, _input: &Input) -> Result<Answer, ParseError> {
+            Ok(Answer::Unsigned(2))
+        }
+    }
+
+    #[test]
+    pub fn test_solve_with_timeout() {
+        let limit = Duration::from_millis(50);
+        let result =
+            solve_with_timeout(Task { day: 30, part: 1 }, &Endless, Input::default(), limit);
+        assert_eq!(result.outcome, Outcome::TimedOut(limit));
+        let result =
+            solve_with_timeout(Task { day: 30, part: 2 }, &Endless, Input::default(), limit);
+        assert_eq!(result.outcome, Outcome::Solved(Answer::Unsigned(2)));
+    }
+}
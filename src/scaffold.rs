@@ -0,0 +1,294 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/**
+ * Generates `src/dayN.rs` from a template and registers it in `lib.rs` and
+ * `solution.rs`, under the crate root `root`. Nothing is written if the day
+ * already exists. Returns the files that were written.
+ */
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day should be between 1 and 25, got {}", day));
+    }
+    let src = root.join("src");
+    let module_path = src.join(format!("day{}.rs", day));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+    let lib_path = src.join("lib.rs");
+    let solution_path = src.join("solution.rs");
+    let read = |p: &Path| {
+        fs::read_to_string(p).map_err(|e| format!("could not read {}: {}", p.display(), e))
+    };
+    // work out every change before writing so a failure leaves the tree alone
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let solution = register_solution(&read(&solution_path)?, day)?;
+
+    let mut written = Vec::new();
+    for (path, contents) in [
+        (module_path, day_module(day)),
+        (lib_path, lib),
+        (solution_path, solution),
+    ] {
+        fs::write(&path, contents)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/**
+ * The source for a new day, with stub parts and example tests to fill in
+ */
+pub fn day_module(day: u8) -> String {
+    TEMPLATE.replace("{day}", &day.to_string())
+}
+
+const TEMPLATE: &str = r#"use crate::error::ParseError;
+use crate::input::Input;
+use crate::solution::{Answer, Example, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    fn day(&self) -> u8 {
+        {day}
+    }
+
+    fn part_1(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(&self, input: &Input) -> Result<Answer, ParseError> {
+        Ok(part_2(input))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+// TODO paste in the example from the puzzle and its answers
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+",
+    part_1: None,
+    part_2: None,
+}];
+
+// TODO solve the parts, until then they're reported as unsolved
+pub fn part_1(_input: &Input) -> Answer {
+    Answer::Unsolved
+}
+
+pub fn part_2(_input: &Input) -> Answer {
+    Answer::Unsolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO remove the ignores once the example answers are filled in
+    #[test]
+    #[ignore]
+    pub fn test_part1_example() {
+        let example = EXAMPLES[0];
+        let answer = part_1(&example.input()).to_string();
+        assert_eq!(example.part_1, Some(answer.as_str()));
+    }
+
+    #[test]
+    #[ignore]
+    pub fn test_part2_example() {
+        let example = EXAMPLES[0];
+        let answer = part_2(&example.input()).to_string();
+        assert_eq!(example.part_2, Some(answer.as_str()));
+    }
+}
+"#;
+
+/**
+ * Adds `pub mod dayN;` to lib.rs, keeping the declarations sorted the way
+ * rustfmt does
+ */
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{}", day);
+    let declaration = format!("pub mod {};", module);
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("{} is already declared in lib.rs", module));
+    }
+    let is_mod = |l: &&str| l.starts_with("pub mod ");
+    let first = lines.iter().position(is_mod).ok_or("no modules found in lib.rs")?;
+    let at = lines[first..]
+        .iter()
+        .position(|l| !is_mod(l) || l[8..].trim_end_matches(';') > module.as_str())
+        .map_or(lines.len(), |i| first + i);
+    lines.insert(at, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/**
+ * Adds the day to the `use crate::{...}` list and the SOLUTIONS registry in
+ * solution.rs
+ */
+pub fn register_solution(solution: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{}", day);
+    let entry = format!("    &{}::Day{},", module, day);
+    if solution.contains(&entry) {
+        return Err(format!("day {} is already registered in solution.rs", day));
+    }
+
+    // the import of the day modules
+    let start = solution.find("use crate::{\n").ok_or("no day module import in solution.rs")?;
+    let end =
+        start + solution[start..].find("};\n").ok_or("unterminated import in solution.rs")? + 3;
+    let mut modules: Vec<&str> = solution[start + 12..end - 3]
+        .split(',')
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+        .collect();
+    modules.push(&module);
+    modules.sort();
+    let import = format_import(&modules);
+
+    // the registry, which is in day order
+    let mut lines: Vec<String> = solution[end..].lines().map(String::from).collect();
+    let first = lines
+        .iter()
+        .position(|l| l.starts_with("pub static SOLUTIONS"))
+        .ok_or("no SOLUTIONS registry in solution.rs")?
+        + 1;
+    let mut at = first;
+    while let Some(existing) = lines.get(at).and_then(|l| registered_day(l)) {
+        if existing > day {
+            break;
+        }
+        at += 1;
+    }
+    lines.insert(at, entry);
+    Ok(format!(
+        "{}{}{}\n",
+        &solution[..start],
+        import,
+        lines.join("\n")
+    ))
+}
+
+// the day of a `    &dayN::DayN,` registry line
+fn registered_day(line: &str) -> Option<u8> {
+    line.trim().strip_prefix("&day")?.split("::").next()?.parse().ok()
+}
+
+// lays the import out over as many lines as rustfmt would
+fn format_import(modules: &[&str]) -> String {
+    let mut out = String::from("use crate::{\n");
+    let mut line = String::from("   ");
+    for module in modules {
+        if line.len() + module.len() + 2 > 100 {
+            out += &line;
+            out.push('\n');
+            line = String::from("   ");
+        }
+        line += &format!(" {},", module);
+    }
+    out + &line + "\n};\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const LIB: &str =
+        "/**\n * docs\n */\npub mod answers;\npub mod day1;\npub mod day2;\npub mod util;\n";
+
+    const SOLUTION: &str = "use std::fmt;
+
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day2, day3, day4, day5, day6, day7,
+    day8, day9,
+};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day9::Day9,
+    &day10::Day10,
+];
+
+fn rest() {}
+";
+
+    #[test]
+    pub fn test_register_module() {
+        let lib = register_module(LIB, 17).unwrap();
+        assert!(lib.contains("pub mod day1;\npub mod day17;\npub mod day2;\n"));
+        let lib = register_module(LIB, 3).unwrap();
+        assert!(lib.contains("pub mod day2;\npub mod day3;\npub mod util;\n"));
+        assert!(register_module(LIB, 2).is_err());
+    }
+
+    #[test]
+    pub fn test_register_solution() {
+        let updated = register_solution(SOLUTION, 17).unwrap();
+        assert!(updated.contains(
+            "use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day2, day3, day4, day5, day6,
+    day7, day8, day9,
+};"
+        ));
+        assert!(updated.contains("    &day10::Day10,\n    &day17::Day17,\n];"));
+        let updated = register_solution(SOLUTION, 5).unwrap();
+        assert!(updated.contains("    &day2::Day2,\n    &day5::Day5,\n    &day9::Day9,"));
+        assert!(updated.ends_with("fn rest() {}\n"));
+        assert!(register_solution(SOLUTION, 9).is_err());
+    }
+
+    #[test]
+    pub fn test_day_module() {
+        let module = day_module(17);
+        assert!(module.contains("pub struct Day17;"));
+        assert!(
+            module.contains("impl Solution for Day17 {\n    fn day(&self) -> u8 {\n        17\n")
+        );
+    }
+
+    #[test]
+    pub fn test_day_module_is_unsolved() {
+        // the stubs must not answer anything that could be recorded or submitted
+        let module = day_module(17);
+        for part in 1..=2 {
+            let stub = format!(
+                "pub fn part_{}(_input: &Input) -> Answer {{\n    Answer::Unsolved\n}}",
+                part
+            );
+            assert!(module.contains(&stub));
+            assert!(module.contains(&format!("Ok(part_{}(input))\n", part)));
+        }
+        assert!(!module.contains("-> u64"));
+    }
+
+    #[test]
+    pub fn test_create_refuses_to_overwrite() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/solution.rs"), SOLUTION).unwrap();
+
+        let written = create(&root, 17).unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(fs::read_to_string(root.join("src/day17.rs")).unwrap().contains("Day17"));
+        assert!(create(&root, 17).is_err());
+        assert!(create(&root, 26).is_err());
+        // an already registered day isn't touched even without its file
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(create(&root, 2).is_err());
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
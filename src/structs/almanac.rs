@@ -1,5 +1,9 @@
 use std::collections::HashSet;
 
+use crate::error::ParseError;
+use crate::input::{Block, Input};
+use crate::util::parse;
+
 /**
  * Day 5 structs for Farmer's almanac
  */
@@ -89,62 +93,11 @@ pub struct Almanac {
 }
 
 impl Almanac {
-    pub fn from(input: &Vec<String>) -> Almanac {
-        let mut result = Almanac {
-            seeds: Vec::new(),
-            maps: Vec::new(),
-        };
-        let mut input_iter = input.iter();
-        // get seeds from the input iter
-        Almanac::parse_seeds(&mut result, &mut input_iter);
-        Almanac::parse_maps(&mut result, &mut input_iter);
-        result
-    }
-
-    fn parse_seeds(result: &mut Almanac, input_iter: &mut std::slice::Iter<'_, String>) {
-        result.seeds = input_iter
-            .next()
-            .unwrap()
-            .split(":")
-            .nth(1)
-            .unwrap()
-            .split_ascii_whitespace()
-            .map(|s| s.parse::<u64>().unwrap())
-            .collect();
-    }
-
-    fn parse_maps(result: &mut Almanac, input_iter: &mut std::slice::Iter<'_, String>) {
-        loop {
-            match input_iter.next() {
-                Some(l) if l.contains("map:") => {
-                    let mut map: Mapping = Mapping {
-                        title: String::from(l.split("map:").nth(0).unwrap().trim()),
-                        ranges: Vec::new(),
-                    };
-                    loop {
-                        match input_iter.next() {
-                            Some(r) if r.trim().len() > 0 => {
-                                let v: Vec<u64> = r
-                                    .split_ascii_whitespace()
-                                    .map(|s| s.parse::<u64>().unwrap())
-                                    .collect();
-                                map.ranges.push(MapRange {
-                                    dest_start: v.get(0).unwrap().clone(),
-                                    src_start: v.get(1).unwrap().clone(),
-                                    range: v.get(2).unwrap().clone(),
-                                });
-                            }
-                            _ => break, // reached end of the map of ranges
-                        }
-                    }
-                    result.maps.push(map);
-                }
-                Some(l) if l.trim().len() == 0 => {
-                    // skip, blank space between inputs
-                }
-                _ => break,
-            }
-        }
+    pub fn from(input: &Input) -> Result<Almanac, ParseError> {
+        let mut blocks = input.blocks();
+        let (_, seeds) = parse_seeds(blocks.next())?;
+        let maps = blocks.map(parse_map).collect::<Result<_, _>>()?;
+        Ok(Almanac { seeds, maps })
     }
 }
 
@@ -155,78 +108,76 @@ pub struct RangedAlmanac {
 }
 
 impl RangedAlmanac {
-    pub fn from(input: &Vec<String>) -> RangedAlmanac {
-        let mut result: RangedAlmanac = RangedAlmanac {
-            seeds: Vec::new(),
-            maps: Vec::new(),
-        };
-        let mut input_iter = input.iter();
-        // get seeds from the input iter
-        RangedAlmanac::parse_ranged_seeds(&mut result, &mut input_iter);
-        RangedAlmanac::parse_ranged_maps(&mut result, &mut input_iter);
-        result
-    }
-    fn parse_ranged_seeds(
-        result: &mut RangedAlmanac,
-        input_iter: &mut std::slice::Iter<'_, String>,
-    ) {
-        let seed_ranges: Vec<u64> = input_iter
-            .next()
-            .unwrap()
-            .split(":")
-            .nth(1)
-            .unwrap()
-            .split_ascii_whitespace()
-            .map(|s| s.parse::<u64>().unwrap())
-            .collect();
-        let mut seeds: Vec<U64range> = Vec::new();
-        let mut seed_iter = seed_ranges.iter();
-        loop {
-            match seed_iter.next() {
-                Some(start) => match seed_iter.next() {
-                    Some(range) => seeds.push(U64range(*start, start.checked_add(*range).unwrap())),
-                    None => panic!("invalid seed range"),
-                },
-                None => break,
-            }
+    pub fn from(input: &Input) -> Result<RangedAlmanac, ParseError> {
+        let mut blocks = input.blocks();
+        let (seeds_block, seed_ranges) = parse_seeds(blocks.next())?;
+        if seed_ranges.len() % 2 != 0 {
+            return Err(ParseError::at_end(&seeds_block[0], "invalid seed range")
+                .on_line(seeds_block.line_number()));
         }
-        result.seeds = seeds;
+        let seeds: Vec<U64range> = seed_ranges
+            .chunks(2)
+            .map(|pair| match pair[0].checked_add(pair[1]) {
+                Some(end) => Ok(U64range(pair[0], end)),
+                None => Err(ParseError::at_end(&seeds_block[0], "seed range overflows")
+                    .on_line(seeds_block.line_number())),
+            })
+            .collect::<Result<_, _>>()?;
+        let maps = blocks.map(parse_map).collect::<Result<_, _>>()?;
+        Ok(RangedAlmanac { seeds, maps })
     }
-    fn parse_ranged_maps(
-        result: &mut RangedAlmanac,
-        input_iter: &mut std::slice::Iter<'_, String>,
-    ) {
-        loop {
-            match input_iter.next() {
-                Some(l) if l.contains("map:") => {
-                    let mut map: Mapping = Mapping {
-                        title: String::from(l.split("map:").nth(0).unwrap().trim()),
-                        ranges: Vec::new(),
-                    };
-                    loop {
-                        match input_iter.next() {
-                            Some(r) if r.trim().len() > 0 => {
-                                let v: Vec<u64> = r
-                                    .split_ascii_whitespace()
-                                    .map(|s| s.parse::<u64>().unwrap())
-                                    .collect();
-                                map.ranges.push(MapRange {
-                                    dest_start: v.get(0).unwrap().clone(),
-                                    src_start: v.get(1).unwrap().clone(),
-                                    range: v.get(2).unwrap().clone(),
-                                });
-                            }
-                            _ => break, // reached end of the map of ranges
-                        }
-                    }
-                    result.maps.push(map);
-                }
-                Some(l) if l.trim().len() == 0 => {
-                    // skip, blank space between inputs
-                }
-                _ => break,
-            }
+}
+
+// the "seeds: 79 14 55 13" line, in a block of its own
+fn parse_seeds(block: Option<Block>) -> Result<(Block, Vec<u64>), ParseError> {
+    let block = block.ok_or_else(|| ParseError::at_end("", "expected a seeds line").on_line(1))?;
+    if let Some(extra) = block.get(1) {
+        return Err(
+            ParseError::at(extra, extra, "expected a blank line after the seeds")
+                .on_line(block.line_number() + 1),
+        );
+    }
+    let (_, seeds) =
+        parse::labelled_numbers(&block[0]).map_err(|e| e.on_line(block.line_number()))?;
+    Ok((block, seeds))
+}
+
+// a "<title> map:" header followed by its ranges
+fn parse_map(block: Block) -> Result<Mapping, ParseError> {
+    let header = &block[0];
+    let title = match header.split_once("map:") {
+        Some((title, _)) => title.trim(),
+        None => {
+            return Err(ParseError::at(header, header, "expected a map header")
+                .on_line(block.line_number()))
         }
+    };
+    let ranges = block
+        .lines()
+        .enumerate()
+        .skip(1)
+        .map(|(i, r)| parse_map_range(r).map_err(|e| e.on_line(block.line_number() + i)))
+        .collect::<Result<_, _>>()?;
+    Ok(Mapping {
+        title: String::from(title),
+        ranges,
+    })
+}
+
+// a "<dest start> <source start> <length>" line
+fn parse_map_range(line: &str) -> Result<MapRange, ParseError> {
+    let v: Vec<u64> = parse::numbers(line, line)?;
+    match v[..] {
+        [dest_start, src_start, range] => Ok(MapRange {
+            dest_start,
+            src_start,
+            range,
+        }),
+        _ => Err(ParseError::at(
+            line,
+            line,
+            "expected <dest start> <source start> <length>",
+        )),
     }
 }
 
@@ -241,96 +192,48 @@ impl Mapping {
      * Maps the input sources to the output destinations represented as a set of
      * ranges.
      *
-     *  any diffs are added back to stack for further processing only if we don't have a full match for the input range
-
-    *  given two ranges
-    *   aaaaaa
-    *       bbbbbbb
-    *  only take upper range
-    * -----
-    *       aaaaaaaa
-    *    bbbbbbb
-    *  only take lower diff
-    *
+     * The part of a range that falls in a map range is mapped to its
+     * destination, and whatever is left either side of it goes back on the
+     * stack to be checked against the other map ranges. Given two ranges
+     *
+     * ```text
+     *   aaaaaaaaaaaa
+     *       bbbb         take the lower and upper leftovers
+     *
+     *       aaaaaaaa
+     *    bbbbbbb         only take the upper leftover
+     * ```
+     *
      * Values that are not mapped to something new from this mapping are output
      * as a range of value that are the same as the input.
      */
     pub fn map_dest(&self, sources: &HashSet<U64range>) -> HashSet<U64range> {
-        // populate stack
-        let mut range_stack: Vec<U64range> = Vec::new();
-        let mut already_processed: HashSet<U64range> = HashSet::new();
+        let mut range_stack: Vec<U64range> = sources.iter().copied().collect();
         let mut results: HashSet<U64range> = HashSet::new();
-        for range in sources.clone() {
-            range_stack.push(range);
-        }
-        // process items off the stack
-        while range_stack.len() > 0 {
-            let current_range = range_stack.pop().unwrap();
-            // println!(
-            //     "map_dest({:?}) Evaluating input range {:?}",
-            //     self.title, current_range
-            // );
-
-            // just in case we end up with some duplicate ranges showing up
-            match already_processed.get(&current_range) {
-                Some(_) => {
-                    // println!("\tAlready processed range {:?}", r);
-                    continue;
-                }
-                None => {
-                    already_processed.insert(current_range);
-                }
+        while let Some(current_range) = range_stack.pop() {
+            if current_range.0 == current_range.1 {
+                continue; // empty, nothing to map
             }
-
-            let mut found_intersection = false;
-            for mr in &self.ranges {
+            let mapped = self.ranges.iter().find_map(|mr| {
                 let map_range = U64range::new(mr.src_start, mr.src_start + mr.range);
-                // println!("\tChecking map range {:?}", map_range);
-                let intersection = U64range::intersect(&map_range, &current_range);
-                let lower_diff = U64range::diff_lower(&map_range, &current_range);
-                let upper_diff = U64range::diff_upper(&map_range, &current_range);
-                match intersection {
-                    Some(r) => {
-                        // intersection is mapped to destination result
-                        // println!("\tFound intersection {:?}", r);
-                        found_intersection = true;
-                        let output_range = mr.get_dest(&r);
-                        results.insert(output_range);
-
-                        if current_range != r {
-                            // println!("\tcurrent range is not fully enclosed in map range");
-                            match lower_diff {
-                                Some(d) if current_range.0 < map_range.0 => {
-                                    // println!(
-                                    //     "\tpushing unmatched lower range {:?} to processing stack",
-                                    //     d
-                                    // );
-                                    range_stack.push(d);
-                                }
-                                _ => {}
-                            }
-                            match upper_diff {
-                                Some(d) if current_range.0 > map_range.0 => {
-                                    // println!(
-                                    //     "\tpushing unmatched upper range {:?} to processing stack",
-                                    //     d
-                                    // );
-                                    range_stack.push(d);
-                                }
-                                _ => {}
-                            }
-                        }
+                U64range::intersect(&map_range, &current_range)
+                    .filter(|i| i.0 < i.1)
+                    .map(|i| (mr, i))
+            });
+            match mapped {
+                Some((mr, intersection)) => {
+                    results.insert(mr.get_dest(&intersection));
+                    if current_range.0 < intersection.0 {
+                        range_stack.push(U64range(current_range.0, intersection.0));
                     }
-                    None => {
-                        // no intersection means this range maps to itself for output
-                        continue;
+                    if intersection.1 < current_range.1 {
+                        range_stack.push(U64range(intersection.1, current_range.1));
                     }
                 }
-            }
-            if !found_intersection {
-                // range didn't map to any inputs
-                // println!("\tNo intersection found with current range, ");
-                results.insert(current_range);
+                // no intersection means this range maps to itself for output
+                None => {
+                    results.insert(current_range);
+                }
             }
         }
         results
@@ -355,6 +258,10 @@ impl MapRange {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -423,6 +330,29 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_parse_errors() {
+        let input = Input::from;
+        let err = Almanac::from(&input("seeds: 79 x4\n\nseed-to-soil map:\n50 98 2")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 11, "x4"));
+        let err = Almanac::from(&input("seeds: 79\n\nseed-to-soil map:\n50 98")).unwrap_err();
+        assert_eq!(err.line, 4);
+        let err = RangedAlmanac::from(&input("seeds: 79 14 55")).unwrap_err();
+        assert_eq!(err.message, "invalid seed range");
+        let err = RangedAlmanac::from(&input("seeds: 18446744073709551615 1")).unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (1, "seed range overflows")
+        );
+        let err = Almanac::from(&input("seeds: 79\n\n \n\nsoil\n50 98 2")).unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (5, "expected a map header")
+        );
+        let almanac = Almanac::from(&input("seeds: 79\n\n\t\na map:\n50 98 2\n\n\n")).unwrap();
+        assert_eq!((almanac.maps.len(), almanac.maps[0].ranges.len()), (1, 1));
+    }
+
     #[test]
     pub fn test_map_range() {
         let input = MapRange {
@@ -434,4 +364,146 @@ mod tests {
         let expected: U64range = U64range::new(53, 57);
         assert_eq!(input.get_dest(&src), expected);
     }
+
+    #[test]
+    pub fn test_map_dest() {
+        // the example's seed-to-soil map
+        let mapping = Mapping {
+            title: String::from("seed-to-soil"),
+            ranges: vec![
+                MapRange {
+                    dest_start: 50,
+                    src_start: 98,
+                    range: 2,
+                },
+                MapRange {
+                    dest_start: 52,
+                    src_start: 50,
+                    range: 48,
+                },
+            ],
+        };
+        // covers both map ranges with seeds left over either side
+        let seeds = U64range(40, 110);
+        let mapped = mapping.map_dest(&HashSet::from([seeds]));
+        let expected = HashSet::from([
+            U64range(40, 50),
+            U64range(52, 100),
+            U64range(50, 52),
+            U64range(100, 110),
+        ]);
+        assert_eq!(mapped, expected);
+    }
+
+    // the properties below are checked point by point, so the ranges are kept
+    // small
+
+    fn points(range: &U64range) -> BTreeSet<u64> {
+        (range.0..range.1).collect()
+    }
+
+    fn all_points<'a>(ranges: impl IntoIterator<Item = &'a U64range>) -> BTreeSet<u64> {
+        ranges.into_iter().flat_map(points).collect()
+    }
+
+    fn small_range() -> impl Strategy<Value = U64range> {
+        (0u64..40, 0u64..20).prop_map(|(start, len)| U64range(start, start + len))
+    }
+
+    // disjoint ranges laid out left to right, from (gap before, length) pairs
+    fn layout(specs: &[(u64, u64)]) -> Vec<U64range> {
+        let mut start = 0;
+        specs
+            .iter()
+            .map(|(gap, len)| {
+                start += gap;
+                let range = U64range(start, start + len);
+                start += len;
+                range
+            })
+            .collect()
+    }
+
+    // map ranges with disjoint sources among the seeds and disjoint
+    // destinations above them, so no two seeds can end up at the same place
+    fn mapping() -> impl Strategy<Value = Mapping> {
+        prop::collection::vec((0u64..10, 0u64..10, 0u64..10), 0..5)
+            .prop_flat_map(|specs| {
+                let order: Vec<usize> = (0..specs.len()).collect();
+                (Just(specs), Just(order).prop_shuffle())
+            })
+            .prop_map(|(specs, order)| {
+                let sources =
+                    layout(&specs.iter().map(|(gap, len, _)| (*gap, *len)).collect::<Vec<_>>());
+                let shuffled: Vec<(u64, u64)> =
+                    order.iter().map(|&i| (specs[i].2, specs[i].1)).collect();
+                let mut destinations = vec![U64range(0, 0); specs.len()];
+                for (&i, dest) in order.iter().zip(layout(&shuffled)) {
+                    destinations[i] = dest;
+                }
+                let ranges = sources
+                    .iter()
+                    .zip(destinations)
+                    .map(|(src, dest)| MapRange {
+                        dest_start: dest.0 + 1000,
+                        src_start: src.0,
+                        range: src.1 - src.0,
+                    })
+                    .collect();
+                Mapping {
+                    title: String::from("test"),
+                    ranges,
+                }
+            })
+    }
+
+    // the mapping applied to a single value
+    fn map_point(mapping: &Mapping, n: u64) -> u64 {
+        mapping
+            .ranges
+            .iter()
+            .find(|mr| mr.src_start <= n && n < mr.src_start + mr.range)
+            .map_or(n, |mr| mr.dest_start + n - mr.src_start)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_intersect(a in small_range(), b in small_range()) {
+            let expected: BTreeSet<u64> = points(&a).intersection(&points(&b)).copied().collect();
+            let intersection = U64range::intersect(&a, &b);
+            prop_assert_eq!(all_points(&intersection), expected);
+            prop_assert_eq!(intersection, U64range::intersect(&b, &a));
+        }
+
+        #[test]
+        fn prop_diffs_cover_both_ranges(a in small_range(), b in small_range()) {
+            let parts = [
+                U64range::diff_lower(&a, &b),
+                U64range::intersect(&a, &b),
+                U64range::diff_upper(&a, &b),
+            ];
+            let parts: Vec<U64range> = parts.into_iter().flatten().collect();
+            let sizes: usize = parts.iter().map(|p| points(p).len()).sum();
+            let covered = all_points(&parts);
+            // no point is in two parts
+            prop_assert_eq!(sizes, covered.len());
+            // and together they're exactly the points in a or b
+            prop_assert_eq!(covered, all_points([&a, &b]));
+        }
+
+        #[test]
+        fn prop_map_dest(
+            mapping in mapping(),
+            seeds in prop::collection::vec((0u64..10, 0u64..20), 0..5),
+        ) {
+            let seeds: HashSet<U64range> = layout(&seeds).into_iter().collect();
+            let mapped = mapping.map_dest(&seeds);
+            let expected: BTreeSet<u64> =
+                all_points(&seeds).into_iter().map(|n| map_point(&mapping, n)).collect();
+            prop_assert_eq!(all_points(&mapped), expected);
+            // every seed is still there, once
+            let total = |ranges: &HashSet<U64range>| ranges.iter().map(|r| r.1 - r.0).sum::<u64>();
+            prop_assert_eq!(total(&mapped), total(&seeds));
+        }
+    }
 }
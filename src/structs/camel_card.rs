@@ -1,5 +1,8 @@
 use std::cmp::Ordering;
 
+use crate::error::ParseError;
+use crate::util::parse;
+
 /**
  * structs for camel cards game
  */
@@ -71,8 +74,8 @@ pub enum HandType {
     FiveOfKind,
 }
 
-pub fn get(c: char) -> CamelCard {
-    return match c {
+pub fn get(c: char) -> Option<CamelCard> {
+    return Some(match c {
         '2' => CamelCard::N2,
         '3' => CamelCard::N3,
         '4' => CamelCard::N4,
@@ -86,31 +89,35 @@ pub fn get(c: char) -> CamelCard {
         'Q' => CamelCard::Q,
         'K' => CamelCard::K,
         'A' => CamelCard::A,
-        _ => {
-            panic!("Invalid char for hand {:?}", c)
-        }
-    };
+        _ => return None,
+    });
 }
 
 pub fn parse_bid(
-    input: &String,
-    hand_parser_strategy: fn(&str, fn(&Vec<CamelCard>) -> HandType) -> Hand,
+    input: &str,
+    hand_parser_strategy: fn(&str, fn(&Vec<CamelCard>) -> HandType) -> Option<Hand>,
     hand_rank_strategy: fn(&Vec<CamelCard>) -> HandType,
-) -> Bid {
-    let mut iter = input.split_ascii_whitespace();
-    let hand = hand_parser_strategy(iter.next().unwrap(), hand_rank_strategy);
-    let amount: u32 = iter.next().unwrap().parse().unwrap();
-    Bid {
+) -> Result<Bid, ParseError> {
+    let (hand, amount) = parse::split_once(input, input, " ", "expected <hand> <bid>")?;
+    let amount: u32 = parse::number(input, amount)?;
+    let hand = hand_parser_strategy(hand, hand_rank_strategy).ok_or_else(|| {
+        ParseError::at(input, hand, "expected a hand of 5 cards from 23456789TJQKA")
+    })?;
+    Ok(Bid {
         hand: hand,
         amount: amount,
-    }
+    })
 }
 
-pub fn parse_hand(h: &str, hand_rank_strategy: fn(&Vec<CamelCard>) -> HandType) -> Hand {
-    let hand: Vec<CamelCard> = h.trim().chars().map(get).collect();
+// None if the hand isn't made up of 5 valid cards
+pub fn parse_hand(h: &str, hand_rank_strategy: fn(&Vec<CamelCard>) -> HandType) -> Option<Hand> {
+    let hand: Vec<CamelCard> = h.trim().chars().map(get).collect::<Option<_>>()?;
+    if hand.len() != 5 {
+        return None;
+    }
     let rank = hand_rank_strategy(&hand);
-    Hand {
+    Some(Hand {
         cards: hand,
         hand_type: rank,
-    }
+    })
 }
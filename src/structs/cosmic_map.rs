@@ -1,15 +1,17 @@
 use core::fmt;
 use std::fmt::Write;
 
+use crate::input::Input;
+
 #[derive(Debug)]
 pub struct CosmicMap {
     coordinates: Vec<Vec<char>>,
 }
 
-impl From<&Vec<String>> for CosmicMap {
-    fn from(value: &Vec<String>) -> Self {
+impl From<&Input> for CosmicMap {
+    fn from(value: &Input) -> Self {
         let result = CosmicMap {
-            coordinates: value.iter().map(|s| s.chars().collect()).collect(),
+            coordinates: value.lines().map(|s| s.chars().collect()).collect(),
         };
         result
     }
@@ -1,3 +1,8 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::input::Input;
+
 /*
 Maze struct used for day 10
  */
@@ -8,14 +13,18 @@ pub struct Maze {
 }
 
 impl Maze {
-    pub fn from(input: &Vec<String>) -> Maze {
-        let row_len: usize = input.get(0).expect("no input").len();
-        let cells: Vec<char> = input.iter().flat_map(|s| s.chars()).collect();
+    pub fn from(input: &Input) -> Result<Maze, ParseError> {
+        // every position is worked out from the row length, so it can't be 0
+        let row_len: usize = match input.get(0) {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(ParseError::at_end("", "expected a grid of pipes").on_line(1)),
+        };
+        let cells: Vec<char> = input.lines().flat_map(|s| s.chars()).collect();
 
-        Maze {
+        Ok(Maze {
             row_len: row_len,
             cells: cells,
-        }
+        })
     }
 
     /** enumerates all valid coordinates in the grid
@@ -120,6 +129,14 @@ impl Maze {
     }
 }
 
+impl fmt::Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<String> =
+            self.cells.chunks(self.row_len).map(|row| row.iter().collect()).collect();
+        f.write_str(&rows.join("\n"))
+    }
+}
+
 #[derive(Debug)]
 pub struct MazePath {
     pub cells: Vec<(usize, usize)>,
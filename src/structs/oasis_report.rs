@@ -1,13 +1,21 @@
+use crate::error::ParseError;
+use crate::input::Input;
+use crate::trace;
+use crate::util::parse;
+
 #[derive(Debug)]
 pub struct OasisReport {
     pub points: Vec<PointHistory>,
 }
 
 impl OasisReport {
-    pub fn from(input: &Vec<String>) -> OasisReport {
-        OasisReport {
-            points: input.iter().map(PointHistory::from).collect(),
-        }
+    pub fn from(input: &Input) -> Result<OasisReport, ParseError> {
+        let points = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| PointHistory::from(line).map_err(|e| e.on_line(i + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(OasisReport { points })
     }
 }
 
@@ -17,10 +25,10 @@ pub struct PointHistory {
 }
 
 impl PointHistory {
-    pub fn from(input: &String) -> PointHistory {
-        PointHistory {
-            values: input.split_ascii_whitespace().map(|c| c.parse::<i64>().unwrap()).collect(),
-        }
+    pub fn from(input: &str) -> Result<PointHistory, ParseError> {
+        Ok(PointHistory {
+            values: parse::numbers(input, input)?,
+        })
     }
 
     pub fn extrapolate(&self) -> i64 {
@@ -34,7 +42,7 @@ impl PointHistory {
 
     pub fn extrapolate_backward(&self) -> i64 {
         let stack = self.get_extrapolation_diffs();
-        println!("{:?}", stack);
+        trace!("{:?}", stack);
         let mut result = 0;
         let mut previous: i64;
         for extrapolation in stack.iter().rev() {
@@ -0,0 +1,373 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::input;
+use crate::runner::{Outcome, Task};
+use crate::solution::Answer;
+
+pub fn history_path() -> PathBuf {
+    input::input_dir().join("guesses.txt")
+}
+
+/**
+ * What the puzzle server made of a submitted answer
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    TooHigh,
+    TooLow,
+    // wrong, without a hint which way
+    Wrong,
+    // how long is left to wait, as the server put it
+    RateLimited(Option<String>),
+    // the part was already solved, or isn't unlocked yet
+    WrongLevel,
+    Unrecognised(String),
+}
+
+impl Response {
+    /**
+     * Reads the response out of the page returned for a submission
+     */
+    pub fn parse(page: &str) -> Response {
+        let text = main_text(page);
+        if text.contains("That's the right answer") {
+            Response::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Response::TooHigh
+            } else if text.contains("your answer is too low") {
+                Response::TooLow
+            } else {
+                Response::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let left = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(left, _)| left.to_string());
+            Response::RateLimited(left)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Response::WrongLevel
+        } else {
+            Response::Unrecognised(text)
+        }
+    }
+
+    // the name a guess with this response is stored under, for the responses
+    // that say something about the answer
+    fn key(&self) -> Option<&'static str> {
+        match self {
+            Response::Correct => Some("correct"),
+            Response::TooHigh => Some("high"),
+            Response::TooLow => Some("low"),
+            Response::Wrong => Some("wrong"),
+            _ => None,
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Response> {
+        match key {
+            "correct" => Some(Response::Correct),
+            "high" => Some(Response::TooHigh),
+            "low" => Some(Response::TooLow),
+            "wrong" => Some(Response::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Correct => f.write_str("correct"),
+            Response::TooHigh => f.write_str("too high"),
+            Response::TooLow => f.write_str("too low"),
+            Response::Wrong => f.write_str("wrong"),
+            Response::RateLimited(Some(left)) => write!(f, "rate limited, {} left to wait", left),
+            Response::RateLimited(None) => f.write_str("rate limited"),
+            Response::WrongLevel => f.write_str("already solved or not unlocked yet"),
+            Response::Unrecognised(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+// the text of the page's <main> element, without tags and with the whitespace
+// collapsed
+fn main_text(page: &str) -> String {
+    let main = match (page.find("<main"), page.find("</main>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in main.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/**
+ * The answer a run would submit, or why there's nothing to submit. A part that
+ * isn't solved yet is always refused, so a stub's answer is never sent.
+ */
+pub fn answer_to_submit(outcome: &Outcome) -> Result<&Answer, String> {
+    match outcome {
+        Outcome::Unsolved | Outcome::Solved(Answer::Unsolved) => {
+            Err(String::from("this part isn't solved yet"))
+        }
+        Outcome::Solved(answer) => Ok(answer),
+        _ => Err(outcome.error().unwrap_or_default()),
+    }
+}
+
+/**
+ * What's already known about an answer from earlier guesses
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    // nothing known against it
+    Fresh,
+    // the same answer was submitted before and got this response
+    Repeat(Response),
+    // another answer was accepted for the part
+    AlreadySolved(String),
+    // a numeric answer outside the bounds earlier guesses set
+    OutOfBounds(String),
+}
+
+/**
+ * Every answer submitted so far and how it was received, kept on disk as
+ * `day part response answer` lines
+ */
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+    guesses: Vec<(Task, Response, String)>,
+}
+
+impl History {
+    pub fn parse(s: &str) -> Result<History, String> {
+        let mut result = History::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let guess = match fields[..] {
+                [day, part, response, answer] => {
+                    match (day.parse(), part.parse(), Response::from_key(response)) {
+                        (Ok(day), Ok(part), Some(response)) => {
+                            Some((Task { day, part }, response, answer.to_string()))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            match guess {
+                Some(guess) => result.guesses.push(guess),
+                None => {
+                    return Err(format!(
+                        "line {}: expected day part response answer: {:?}",
+                        i + 1,
+                        line
+                    ))
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /**
+     * Loads the history from disk. A missing file means nothing was guessed yet.
+     */
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<History, String> {
+        match fs::read_to_string(&path) {
+            Ok(s) => History::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.as_ref().display(), e)),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = String::from("# day part response answer\n");
+        for (task, response, answer) in &self.guesses {
+            if let Some(key) = response.key() {
+                out += &format!("{} {} {} {}\n", task.day, task.part, key, answer);
+            }
+        }
+        fs::write(path, out)
+    }
+
+    /**
+     * Records a guess, if the response says anything about the answer
+     */
+    pub fn record(&mut self, task: Task, answer: &str, response: &Response) {
+        if response.key().is_some() {
+            self.guesses.push((task, response.clone(), answer.to_string()));
+        }
+    }
+
+    /**
+     * The guesses for a part, oldest first
+     */
+    pub fn guesses(&self, task: Task) -> impl Iterator<Item = (&Response, &str)> {
+        self.guesses
+            .iter()
+            .filter(move |(t, _, _)| *t == task)
+            .map(|(_, response, answer)| (response, answer.as_str()))
+    }
+
+    /**
+     * Checks a new answer for a part against the earlier guesses
+     */
+    pub fn check(&self, task: Task, answer: &str) -> Check {
+        if let Some((response, _)) = self.guesses(task).find(|(_, a)| *a == answer) {
+            return Check::Repeat(response.clone());
+        }
+        if let Some((_, correct)) = self.guesses(task).find(|(r, _)| **r == Response::Correct) {
+            return Check::AlreadySolved(correct.to_string());
+        }
+        let value: i128 = match answer.parse() {
+            Ok(v) => v,
+            Err(_) => return Check::Fresh,
+        };
+        // the tightest bounds, as (value, answer) so the message shows the guess
+        let bound = |wanted: &Response| {
+            self.guesses(task)
+                .filter(|(r, _)| *r == wanted)
+                .filter_map(|(_, a)| a.parse::<i128>().ok().map(|v| (v, a)))
+                .collect::<Vec<_>>()
+        };
+        let lowest_high = bound(&Response::TooHigh).into_iter().min();
+        let highest_low = bound(&Response::TooLow).into_iter().max();
+        match (lowest_high, highest_low) {
+            (Some((high, guess)), _) if value >= high => Check::OutOfBounds(format!(
+                "{} is no lower than {}, which was too high",
+                answer, guess
+            )),
+            (_, Some((low, guess))) if value <= low => Check::OutOfBounds(format!(
+                "{} is no higher than {}, which was too low",
+                answer, guess
+            )),
+            _ => Check::Fresh,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><header>Advent of Code</header><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    pub fn test_parse_response() {
+        assert_eq!(
+            Response::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Response::Correct
+        );
+        assert_eq!(
+            Response::parse(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Response::TooHigh
+        );
+        assert_eq!(
+            Response::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Response::TooLow
+        );
+        assert_eq!(
+            Response::parse(&page("That's not the right answer. If you're stuck, ...")),
+            Response::Wrong
+        );
+        assert_eq!(
+            Response::parse(&page(
+                "You gave an answer too recently. You have 4m 12s left to wait."
+            )),
+            Response::RateLimited(Some(String::from("4m 12s")))
+        );
+        assert_eq!(
+            Response::parse(&page("You don't seem to be solving the right level.")),
+            Response::WrongLevel
+        );
+        assert_eq!(
+            Response::parse(&page("Something <b>else</b>")),
+            Response::Unrecognised(String::from("Something else"))
+        );
+    }
+
+    #[test]
+    pub fn test_round_trip() {
+        let task = Task { day: 5, part: 2 };
+        let mut history = History::default();
+        history.record(task, "100", &Response::TooHigh);
+        history.record(task, "9", &Response::RateLimited(None));
+        history.record(task, "50", &Response::Correct);
+
+        let path = std::env::temp_dir().join(format!("aoc-guesses-{}.txt", std::process::id()));
+        history.save(&path).unwrap();
+        let loaded = History::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, history);
+        assert_eq!(loaded.guesses(task).count(), 2);
+        assert!(History::parse("5 2 maybe 7").is_err());
+    }
+
+    #[test]
+    pub fn test_answer_to_submit() {
+        let solved = Outcome::Solved(Answer::Unsigned(42));
+        assert_eq!(answer_to_submit(&solved), Ok(&Answer::Unsigned(42)));
+        let refused = Err(String::from("this part isn't solved yet"));
+        assert_eq!(answer_to_submit(&Outcome::Unsolved), refused);
+        assert_eq!(
+            answer_to_submit(&Outcome::Solved(Answer::Unsolved)),
+            refused
+        );
+        let failed = Outcome::Failed(String::from("boom"));
+        assert!(answer_to_submit(&failed).is_err());
+    }
+
+    #[test]
+    pub fn test_check() {
+        let task = Task { day: 1, part: 1 };
+        let history =
+            History::parse("1 1 high 100\n1 1 high 80\n1 1 low 20\n1 1 wrong 50\n").unwrap();
+        assert_eq!(history.check(task, "50"), Check::Repeat(Response::Wrong));
+        assert_eq!(history.check(task, "40"), Check::Fresh);
+        assert_eq!(
+            history.check(task, "90"),
+            Check::OutOfBounds(String::from("90 is no lower than 80, which was too high"))
+        );
+        assert_eq!(
+            history.check(task, "15"),
+            Check::OutOfBounds(String::from("15 is no higher than 20, which was too low"))
+        );
+        assert_eq!(history.check(Task { day: 1, part: 2 }, "90"), Check::Fresh);
+
+        let history = History::parse("1 1 correct 42").unwrap();
+        assert_eq!(history.check(task, "42"), Check::Repeat(Response::Correct));
+        assert_eq!(
+            history.check(task, "43"),
+            Check::AlreadySolved(String::from("42"))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/**
+ * Where a sequence of states starts repeating. The states after 0 to
+ * `start - 1` steps are a lead-in, then the rest loop round every `period`
+ * steps.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /**
+     * The number of steps, before the sequence first comes back round, to the
+     * same state as after `n` steps
+     */
+    pub fn index_of(&self, n: usize) -> usize {
+        match n < self.start {
+            true => n,
+            false => self.start + (n - self.start) % self.period,
+        }
+    }
+}
+
+/**
+ * Steps from `initial` until a state repeats. Only ends if the states do
+ * repeat, so `step` shouldn't be able to wander off forever.
+ */
+pub fn detect<S: Hash + Eq>(initial: S, step: impl FnMut(&S) -> S) -> Cycle {
+    walk(initial, step, usize::MAX).1.expect("the walk only stops at a repeat")
+}
+
+/**
+ * The state after applying `step` to `initial` `n` times, skipping the whole
+ * loops once the states start repeating
+ */
+pub fn state_after<S: Hash + Eq>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (mut states, cycle) = walk(initial, step, n);
+    states.swap_remove(cycle.map_or(n, |c| c.index_of(n)))
+}
+
+// every state from `initial` on, until one repeats or `limit` steps have been
+// taken. states[i] is the state after i steps. Each state is kept once, with
+// their hashes pointing back at them to spot the repeat.
+fn walk<S: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> (Vec<S>, Option<Cycle>) {
+    let hasher = RandomState::new();
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let i = states.len() - 1;
+        let bucket = seen.entry(hasher.hash_one(&states[i])).or_default();
+        if let Some(&start) = bucket.iter().find(|j| states[**j] == states[i]) {
+            // the repeat is the state at start again
+            states.pop();
+            return (
+                states,
+                Some(Cycle {
+                    start,
+                    period: i - start,
+                }),
+            );
+        }
+        bucket.push(i);
+        if i == limit {
+            return (states, None);
+        }
+        let next = step(&states[i]);
+        states.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    pub fn test_detect() {
+        // 0 1 2 3 4 2 3 4 ...
+        let step = |x: &u32| if *x == 4 { 2 } else { x + 1 };
+        let cycle = detect(0, step);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 2,
+                period: 3
+            }
+        );
+        assert_eq!(
+            (cycle.index_of(1), cycle.index_of(5), cycle.index_of(9)),
+            (1, 2, 3)
+        );
+        assert_eq!(
+            detect(7, |x: &u32| *x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_state_after() {
+        let step = |x: &u32| if *x == 4 { 2 } else { x + 1 };
+        assert_eq!(state_after(0, step, 0), 0);
+        assert_eq!(state_after(0, step, 3), 3);
+        assert_eq!(state_after(0, step, 1_000_000_000), 4);
+        // states that never repeat are fine as long as n is reached
+        assert_eq!(state_after(0u64, |x| x + 1, 10), 10);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_state_after(a in 1u64..50, b in 0u64..50, m in 1u64..50, x in 0u64..50, n in 0usize..500) {
+            let step = |x: &u64| (a * x + b) % m;
+            let mut expected = x;
+            for _ in 0..n {
+                expected = step(&expected);
+            }
+            prop_assert_eq!(state_after(x, step, n), expected);
+        }
+    }
+}
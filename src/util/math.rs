@@ -0,0 +1,231 @@
+use std::fmt;
+use std::ops::{Div, Mul, Rem};
+
+/**
+ * The unsigned integer types gcd and lcm work over
+ */
+pub trait Unsigned:
+    Copy + Eq + Ord + Rem<Output = Self> + Div<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {$(
+        impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+unsigned!(u64, u128);
+
+/**
+ * The greatest common divisor, by Euclid's algorithm. gcd(0, 0) is 0
+ */
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/**
+ * The least common multiple. lcm(0, n) is 0. Overflows like `*` does, see
+ * `checked_lcm`
+ */
+pub fn lcm<T: Unsigned>(a: T, b: T) -> T {
+    match a == T::ZERO || b == T::ZERO {
+        true => T::ZERO,
+        false => a / gcd(a, b) * b,
+    }
+}
+
+/**
+ * The least common multiple, or None if it doesn't fit in `T`
+ */
+pub fn checked_lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    match a == T::ZERO || b == T::ZERO {
+        true => Some(T::ZERO),
+        false => (a / gcd(a, b)).checked_mul(b),
+    }
+}
+
+/**
+ * The least common multiple of all of `values`, 1 if there are none
+ */
+pub fn lcm_of<T: Unsigned>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/**
+ * The least common multiple of all of `values`, or None if it doesn't fit in `T`
+ */
+pub fn checked_lcm_of<T: Unsigned>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().try_fold(T::ONE, checked_lcm)
+}
+
+/**
+ * (g, x, y) where g is gcd(a, b) and a * x + b * y = g
+ */
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/**
+ * Why a set of congruences couldn't be solved
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    // two of the congruences contradict each other
+    NoSolution,
+    // the combined modulus doesn't fit in a u64
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::NoSolution => write!(f, "the congruences have no common solution"),
+            CrtError::Overflow => write!(f, "the combined modulus overflows a u64"),
+        }
+    }
+}
+
+/**
+ * Solves x = r (mod m) for every (r, m) in `congruences` by the Chinese
+ * Remainder Theorem, giving (x, modulus) with the smallest such x. The moduli
+ * don't have to be coprime, the result's modulus is their lcm. Moduli must be
+ * positive.
+ *
+ *  crt(&[(2, 3), (3, 5), (2, 7)]) == Ok((23, 105))
+ */
+pub fn crt(congruences: &[(u64, u64)]) -> Result<(u64, u64), CrtError> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        assert!(m2 > 0, "modulus must be positive");
+        combine(r1, m1, r2 % m2, m2)
+    })
+}
+
+// the solution to x = r1 (mod m1) and x = r2 (mod m2), with r1 < m1 and r2 < m2
+fn combine(r1: u64, m1: u64, r2: u64, m2: u64) -> Result<(u64, u64), CrtError> {
+    let (g, p, _) = extended_gcd(m1 as i128, m2 as i128);
+    let diff = r2 as i128 - r1 as i128;
+    if diff % g != 0 {
+        return Err(CrtError::NoSolution);
+    }
+    let m2_g = (m2 as i128 / g) as u128;
+    let modulus = checked_lcm(m1, m2).ok_or(CrtError::Overflow)?;
+    // m1 * p = g (mod m2), so stepping r1 by m1 * diff / g * p lands on r2.
+    // both factors are below m2 / g so their product fits in a u128
+    let steps = (diff / g).rem_euclid(m2_g as i128) as u128;
+    let k = steps * p.rem_euclid(m2_g as i128) as u128 % m2_g;
+    let x = r1 as u128 + m1 as u128 * k;
+    Ok((x as u64, modulus))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    pub fn test_gcd_lcm() {
+        assert_eq!(gcd(48u64, 18), 6);
+        assert_eq!((gcd(0u64, 7), gcd(0u64, 0)), (7, 0));
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(6u64, 3), 6);
+        assert_eq!(lcm(0u128, 3), 0);
+        assert_eq!(lcm_of([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_of(Vec::<u64>::new()), 1);
+        assert_eq!(checked_lcm(u64::MAX, 2), None);
+        assert_eq!(checked_lcm(u64::MAX as u128, 2), Some(u64::MAX as u128 * 2));
+        assert_eq!(
+            checked_lcm_of([
+                3,
+                u64::MAX - 1,
+                u64::MAX
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    pub fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+        assert_eq!(extended_gcd(0, 0), (0, 1, 0));
+    }
+
+    #[test]
+    pub fn test_crt() {
+        assert_eq!(
+            crt(&[
+                (2, 3),
+                (3, 5),
+                (2, 7)
+            ]),
+            Ok((23, 105))
+        );
+        // moduli sharing factors
+        assert_eq!(
+            crt(&[
+                (3, 4),
+                (5, 6)
+            ]),
+            Ok((11, 12))
+        );
+        assert_eq!(
+            crt(&[
+                (1, 4),
+                (2, 6)
+            ]),
+            Err(CrtError::NoSolution)
+        );
+        assert_eq!(crt(&[(10, 7)]), Ok((3, 7)));
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(
+            crt(&[
+                (0, u64::MAX),
+                (1, 2)
+            ]),
+            Err(CrtError::Overflow)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_extended_gcd(a in -1_000_000i128..1_000_000, b in -1_000_000i128..1_000_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(a * x + b * y, g);
+            prop_assert_eq!(g as u128, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        }
+
+        #[test]
+        fn prop_crt(x in 0u64..1 << 40, moduli in prop::collection::vec(1u64..1000, 1..5)) {
+            let congruences: Vec<(u64, u64)> = moduli.iter().map(|m| (x % m, *m)).collect();
+            let (r, m) = crt(&congruences).unwrap();
+            prop_assert_eq!(m, lcm_of(moduli.iter().copied()));
+            prop_assert_eq!(r, x % m);
+        }
+    }
+}
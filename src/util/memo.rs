@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/**
+ * A memoization cache that a solver creates for a run and passes down to the
+ * functions it speeds up, so nothing is remembered between runs and a
+ * benchmark's timings don't depend on what ran before it. Keeps count of how
+ * often lookups found an answer.
+ *
+ *  if let Some(n) = memo.get(&key) {
+ *      return n;
+ *  }
+ *  let n = ...;
+ *  memo.insert(key, n)
+ */
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    entries: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            entries: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /**
+     * The remembered value for `key`, counted as a hit or a miss
+     */
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get(key).cloned();
+        match value {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        value
+    }
+
+    /**
+     * Remembers `value` for `key`, handing it back to return
+     */
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.entries.insert(key, value.clone());
+        value
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /**
+     * Forgets every entry and resets the counts
+     */
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+impl<K, V> fmt::Display for Memo<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} entries, {} hits, {} misses",
+            self.entries.len(),
+            self.hits,
+            self.misses
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        if let Some(f) = memo.get(&n) {
+            return f;
+        }
+        let f = fib(n - 1, memo) + fib(n - 2, memo);
+        memo.insert(n, f)
+    }
+
+    #[test]
+    pub fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(50, &mut memo), 12586269025);
+        assert_eq!((memo.len(), memo.misses(), memo.hits()), (49, 49, 47));
+        assert_eq!(memo.to_string(), "49 entries, 47 hits, 49 misses");
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!((memo.hits(), memo.misses()), (0, 0));
+    }
+}
@@ -0,0 +1,18 @@
+use std::fs::read_to_string;
+use std::io;
+use std::path::Path;
+
+use crate::input::Input;
+
+pub mod cycle;
+pub mod math;
+pub mod memo;
+pub mod parse;
+
+pub fn read_input<P: AsRef<Path>>(p: P) -> Input {
+    try_read_input(p).unwrap() // panic on possible file-reading errors
+}
+
+pub fn try_read_input<P: AsRef<Path>>(p: P) -> io::Result<Input> {
+    Ok(Input::new(read_to_string(p)?))
+}
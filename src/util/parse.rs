@@ -0,0 +1,163 @@
+use std::str::FromStr;
+
+use crate::error::{self, ParseError};
+
+// Small parsers for the shapes that keep turning up in puzzle inputs. Each one
+// takes the whole `line` alongside the piece of it being parsed (a slice of
+// `line`) so errors point at the right column. As with `ParseError::at`, the
+// line number is left at 0 for the caller to fill in with `on_line`.
+
+/**
+ * Parses `s`, ignoring surrounding whitespace, as a number
+ */
+pub fn number<T: FromStr>(line: &str, s: &str) -> Result<T, ParseError> {
+    error::parse_number(line, s.trim())
+}
+
+/**
+ * Whitespace separated numbers, "1 2  3" is [1, 2, 3]
+ */
+pub fn numbers<T: FromStr>(line: &str, s: &str) -> Result<Vec<T>, ParseError> {
+    s.split_ascii_whitespace().map(|n| error::parse_number(line, n)).collect()
+}
+
+/**
+ * Splits `s` around the first `sep`, with `message` reported at the end of `s`
+ * if there isn't one
+ */
+pub fn split_once<'a>(
+    line: &str,
+    s: &'a str,
+    sep: &str,
+    message: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(sep).ok_or_else(|| ParseError::at(line, &s[s.len()..], message))
+}
+
+/**
+ * Values separated by `sep`, each trimmed and handed to `item`. Empty values,
+ * such as from a trailing separator, are an error.
+ * "1, 2,3" split on ',' gives "1", "2" and "3"
+ */
+pub fn separated<'a, T>(
+    line: &str,
+    s: &'a str,
+    sep: char,
+    mut item: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    s.split(sep)
+        .map(|v| match v.trim() {
+            "" => Err(ParseError::at(
+                line,
+                v,
+                format!("expected a value around {:?}", sep),
+            )),
+            v => item(v),
+        })
+        .collect()
+}
+
+/**
+ * Numbers separated by `sep`, "1,1,3" split on ',' is [1, 1, 3]
+ */
+pub fn separated_numbers<T: FromStr>(line: &str, s: &str, sep: char) -> Result<Vec<T>, ParseError> {
+    separated(line, s, sep, |n| error::parse_number(line, n))
+}
+
+/**
+ * A "label: 1 2 3" line, giving ("label", [1, 2, 3])
+ */
+pub fn labelled_numbers<T: FromStr>(line: &str) -> Result<(&str, Vec<T>), ParseError> {
+    let (label, values) = split_once(line, line, ":", "expected ':' after the label")?;
+    Ok((label.trim(), numbers(line, values)?))
+}
+
+/**
+ * A "label: a; b; c" line, giving ("label", ["a", "b", "c"])
+ */
+pub fn record(line: &str, sep: char) -> Result<(&str, Vec<&str>), ParseError> {
+    let (label, values) = split_once(line, line, ":", "expected ':' after the label")?;
+    Ok((label.trim(), separated(line, values, sep, Ok)?))
+}
+
+/**
+ * A "key = (a, b)" line, giving ("key", ("a", "b"))
+ */
+pub fn key_pair(line: &str) -> Result<(&str, (&str, &str)), ParseError> {
+    let (key, pair) = split_once(line, line, "=", "expected '=' after the key")?;
+    let key = non_empty(line, key, "expected a key before '='")?;
+    let pair = pair.trim();
+    let inner = pair
+        .strip_prefix('(')
+        .and_then(|p| p.strip_suffix(')'))
+        .ok_or_else(|| ParseError::at(line, pair, "expected a pair like (a, b)"))?;
+    let (a, b) = split_once(line, inner, ",", "expected ',' between the pair")?;
+    let a = non_empty(line, a, "expected a value before ','")?;
+    let b = non_empty(line, b, "expected a value after ','")?;
+    Ok((key, (a, b)))
+}
+
+// `s` trimmed, or `message` if that leaves nothing
+fn non_empty<'a>(line: &str, s: &'a str, message: &str) -> Result<&'a str, ParseError> {
+    match s.trim() {
+        "" => Err(ParseError::at(line, s, message)),
+        s => Ok(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_numbers() {
+        let line = "Time:  7 15   30";
+        assert_eq!(labelled_numbers::<u32>(line), Ok(("Time", vec![7, 15, 30])));
+        assert_eq!(numbers::<i64>(line, "-1 2"), Ok(vec![-1, 2]));
+        assert_eq!(
+            separated_numbers::<usize>(line, "1,1, 3", ','),
+            Ok(vec![1, 1, 3])
+        );
+        assert_eq!(number::<u8>(line, &line[6..9]), Ok(7));
+
+        let err = labelled_numbers::<u32>("Time 7 15").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (10, "expected ':' after the label")
+        );
+        let err = labelled_numbers::<u32>("Time: 7 x5").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (9, "x5"));
+        let line = "1,,3";
+        let err = separated_numbers::<u32>(line, line, ',').unwrap_err();
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    pub fn test_record() {
+        let line = "Game 1: 3 blue, 4 red; 1 red";
+        assert_eq!(
+            record(line, ';'),
+            Ok((
+                "Game 1",
+                vec![
+                    "3 blue, 4 red",
+                    "1 red"
+                ]
+            ))
+        );
+        let err = record("Game 1: 3 blue;", ';').unwrap_err();
+        assert_eq!(err.column, 16);
+    }
+
+    #[test]
+    pub fn test_key_pair() {
+        assert_eq!(key_pair("AAA = (BBB, CCC)"), Ok(("AAA", ("BBB", "CCC"))));
+        assert_eq!(key_pair("11A=(11B,XXX)"), Ok(("11A", ("11B", "XXX"))));
+        let err = key_pair("AAA = BBB").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (7, "BBB"));
+        let err = key_pair("AAA = (BBB CCC)").unwrap_err();
+        assert_eq!(err.column, 15);
+        let err = key_pair(" = (BBB, CCC)").unwrap_err();
+        assert_eq!(err.message, "expected a key before '='");
+    }
+}
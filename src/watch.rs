@@ -0,0 +1,89 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/**
+ * Notices changes to a set of files by polling their modification times. A
+ * file that doesn't exist yet counts as changed once it's created, so a watch
+ * can be started before the input has been saved.
+ */
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    /**
+     * Starts watching `paths`, taking their current state as unchanged
+     */
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            files: paths
+                .into_iter()
+                .map(|p| {
+                    let last = modified(&p);
+                    (p, last)
+                })
+                .collect(),
+        }
+    }
+
+    /**
+     * The files that changed since the last call, or since the watch started
+     */
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in self.files.iter_mut() {
+            let now = modified(path);
+            if now != *last {
+                *last = now;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /**
+     * Blocks until at least one file changes, checking every `interval`
+     */
+    pub fn wait(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            let changed = self.changed();
+            if !changed.is_empty() {
+                return changed;
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs::File};
+
+    use super::*;
+
+    #[test]
+    pub fn test_changed() {
+        let path = env::temp_dir().join(format!("aoc-watch-test-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "1\n").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // set the time explicitly, as two writes can land in the same tick
+        let later = SystemTime::now() + Duration::from_secs(60);
+        File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert_eq!(watcher.wait(Duration::from_millis(1)), vec![path.clone()]);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+use adventofcode2023::input::{Input, InputSource};
+use adventofcode2023::runner::{self, Outcome, Task};
+use adventofcode2023::solution::{self, Answer};
+use adventofcode2023::structs::almanac::U64range;
+use adventofcode2023::structs::cosmic_map::CosmicMap;
+use adventofcode2023::structs::maze::Maze;
+use adventofcode2023::{day2, day9};
+
+#[test]
+pub fn test_day_functions() {
+    let input = Input::from("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45");
+    assert_eq!(day9::part_1(&input), Ok(114));
+    assert_eq!(day9::part_2(&input), Ok(2));
+
+    let bad = Input::from("Game 1: 3 blue\nGame 2: three red");
+    let err = day2::part_1(&bad).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 9));
+}
+
+#[test]
+pub fn test_registry() {
+    let day9 = solution::find(9).unwrap();
+    let example = day9.examples_for(1)[0];
+    assert_eq!(
+        day9.solve(1, &example.input()),
+        Some(Ok(Answer::Signed(114)))
+    );
+}
+
+#[test]
+pub fn test_runner() {
+    let result = runner::run(Task { day: 4, part: 2 }, &InputSource::Example(1), None);
+    assert_eq!(result.outcome, Outcome::Solved(Answer::Unsigned(30)));
+}
+
+#[test]
+pub fn test_structs() {
+    assert_eq!(
+        U64range::intersect(&U64range(1, 5), &U64range(3, 7)),
+        Some(U64range(3, 5))
+    );
+
+    let maze = Maze::from(&Input::from(".....\n.S-7.\n.|.|.\n.L-J.\n.....")).unwrap();
+    assert_eq!(maze.find(&'S'), Some((1, 1)));
+
+    let map = CosmicMap::from(&Input::from("#..\n...\n..#"));
+    assert_eq!(map.get_empty_rows(), vec![1]);
+    assert_eq!(map.get_empty_cols(), vec![1]);
+}
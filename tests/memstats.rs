@@ -0,0 +1,24 @@
+use adventofcode2023::memstats::{self, CountingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+pub fn test_measure_counts_allocations() {
+    memstats::enable();
+    let (v, stats) = memstats::measure(|| {
+        let v: Vec<u64> = Vec::with_capacity(128);
+        let scratch = vec![0_u8; 4096];
+        drop(scratch);
+        v
+    });
+    let stats = stats.unwrap();
+    assert_eq!(v.capacity(), 128);
+    assert_eq!(stats.allocations, 2);
+    assert_eq!(stats.bytes, 128 * 8 + 4096);
+    assert_eq!(stats.peak, 128 * 8 + 4096);
+
+    // nothing allocated means nothing counted
+    let (_, stats) = memstats::measure(|| 1 + 1);
+    assert_eq!(stats.unwrap().allocations, 0);
+}